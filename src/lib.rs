@@ -1,18 +1,77 @@
+use base58::{FromBase58, ToBase58};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use blake2::digest::consts::U32;
+use blake2::{Blake2b, Blake2b512, Digest};
+use crypto_secretbox::aead::{Aead, KeyInit};
+use crypto_secretbox::{Key, Nonce, XSalsa20Poly1305};
 use hex;
+use hmac::{Hmac, Mac};
+use pyo3::create_exception;
 use pyo3::prelude::*;
-use pyo3::types::{PyBytes, PyDict, PyList, PyString};
+use pyo3::types::{PyBool, PyBytes, PyDict, PyList, PyString};
 use pyo3_asyncio::tokio::future_into_py;
+use schnorrkel::derive::{ChainCode, Derivation};
+use schnorrkel::ExpansionMode;
+use secp256k1::ecdsa::{RecoverableSignature, RecoveryId};
+use secp256k1::{Message, Scalar, SecretKey, SECP256K1};
+use sha2::Sha512;
+use sha3::Keccak256;
+use std::collections::{HashMap, VecDeque};
+use std::convert::TryInto;
+use std::str::FromStr;
 use std::sync::Arc;
+use subxt::backend::legacy::LegacyRpcMethods;
+use subxt::backend::rpc::RpcClient;
 use subxt::backend::StreamOfResults;
-use subxt::blocks::Block;
-use subxt::config::polkadot::PolkadotExtrinsicParamsBuilder as Params;
-use subxt::dynamic::{tx, Value};
-use subxt::ext::scale_value::{Composite, Primitive, ValueDef};
-use subxt::storage::{DynamicAddress, StorageKeyValuePair};
-use subxt::tx::Signer as SignerT;
+use subxt::blocks::{Block, ExtrinsicEvents};
+use subxt::client::{ClientState, RuntimeVersion};
+use subxt::config::signed_extensions::{
+    ChargeAssetTxPayment, ChargeTransactionPayment, CheckGenesis, CheckMetadataHash,
+    CheckMortality, CheckNonce, CheckSpecVersion, CheckTxVersion, SignedExtension,
+};
+use subxt::config::substrate::{
+    BlakeTwo256, SubstrateExtrinsicParams, SubstrateExtrinsicParamsBuilder as Params,
+    SubstrateHeader,
+};
+use subxt::config::{
+    ExtrinsicParams, ExtrinsicParamsEncoder, ExtrinsicParamsError, RefineParams, RefineParamsData,
+};
+use subxt::dynamic::{tx, DecodedValueThunk, Value};
+use subxt::events::EventDetails;
+use subxt::ext::codec::{Decode, Encode, Output};
+use subxt::ext::scale_encode::EncodeAsType;
+use subxt::ext::scale_info::TypeDef;
+use subxt::ext::scale_value::scale::decode_as_type;
+use subxt::ext::scale_value::{At, Composite, Primitive, ValueDef};
+use subxt::metadata::types::{
+    SignedExtensionMetadata, StorageEntryMetadata, StorageEntryModifier, StorageEntryType,
+    StorageHasher,
+};
+use subxt::tx::{
+    PartialExtrinsic, Payload, Signer as SignerT, SubmittableExtrinsic, TxProgress, TxStatus,
+};
+use subxt::utils::{AccountId32, MultiAddress, MultiSignature, H256};
 use subxt::Config;
-use subxt::{OnlineClient, PolkadotConfig};
-use subxt_signer::sr25519::{Keypair as STKeypair, PublicKey, Signature};
+use subxt::{OfflineClient, OnlineClient};
+use subxt_signer::bip39::Mnemonic;
+use subxt_signer::sr25519::Keypair as STKeypair;
+use subxt_signer::{DeriveJunction, ExposeSecret, SecretUri, DEV_PHRASE};
+
+/// The number of keys queried per `state_queryStorageAt` request.
+const STORAGE_QUERY_CHUNK_SIZE: usize = 256;
+
+/// The default number of keys fetched per page by `StorageIterator`, as in subxt.
+const STORAGE_PAGE_SIZE: u32 = 32;
+
+/// The node `SubxtClient.new` connects to, as `OnlineClient::new` does.
+const DEFAULT_URL: &str = "ws://127.0.0.1:9944";
+
+/// The XCM version `DryRunApi.dry_run_call` reports forwarded messages in.
+const DRY_RUN_XCM_VERSION: u32 = 4;
+
+/// The metadata version requested from the runtime, matching what subxt decodes by default.
+const METADATA_VERSION: u32 = 15;
 
 #[derive(Clone)]
 enum AddressUse {
@@ -20,13 +79,519 @@ enum AddressUse {
     Extrinsic,
 }
 
+/// The signature scheme used by a Keypair.
+#[derive(Clone, Copy)]
+enum KeyScheme {
+    Sr25519,
+    Ed25519,
+    Ecdsa,
+    Ethereum,
+}
+
+impl KeyScheme {
+    /// Parse a scheme name as accepted by the `scheme=` argument on Keypair constructors.
+    fn parse(scheme: &str) -> PyResult<Self> {
+        match scheme.to_lowercase().as_str() {
+            "sr25519" => Ok(KeyScheme::Sr25519),
+            "ed25519" => Ok(KeyScheme::Ed25519),
+            "ecdsa" | "secp256k1" => Ok(KeyScheme::Ecdsa),
+            "ethereum" | "eth" => Ok(KeyScheme::Ethereum),
+            _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Unsupported key scheme: {}",
+                scheme
+            ))),
+        }
+    }
+
+    /// The canonical name of the scheme, as returned by `Keypair.scheme`.
+    fn name(self) -> &'static str {
+        match self {
+            KeyScheme::Sr25519 => "sr25519",
+            KeyScheme::Ed25519 => "ed25519",
+            KeyScheme::Ecdsa => "ecdsa",
+            KeyScheme::Ethereum => "ethereum",
+        }
+    }
+
+    /// Wrap a raw signature produced with this scheme for use in an extrinsic.
+    fn signature(self, signature: &[u8]) -> PyResult<ChainSignature> {
+        let invalid_length = |_| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Invalid signature length {} for scheme {}",
+                signature.len(),
+                self.name()
+            ))
+        };
+        Ok(match self {
+            KeyScheme::Sr25519 => ChainSignature::Multi(MultiSignature::Sr25519(
+                signature.try_into().map_err(invalid_length)?,
+            )),
+            KeyScheme::Ed25519 => ChainSignature::Multi(MultiSignature::Ed25519(
+                signature.try_into().map_err(invalid_length)?,
+            )),
+            KeyScheme::Ecdsa => ChainSignature::Multi(MultiSignature::Ecdsa(
+                signature.try_into().map_err(invalid_length)?,
+            )),
+            KeyScheme::Ethereum => {
+                ChainSignature::Ethereum(signature.try_into().map_err(invalid_length)?)
+            }
+        })
+    }
+
+    /// The scheme assumed for an account when none is given.
+    fn default_for(account_id: &ChainAccountId) -> Self {
+        match account_id {
+            ChainAccountId::Id32(_) => KeyScheme::Sr25519,
+            ChainAccountId::Id20(_) => KeyScheme::Ethereum,
+        }
+    }
+}
+
+/// Return the 32-byte seed for a BIP-39 mnemonic, as Substrate does for all of its key types.
+fn phrase_seed(mnemonic: &Mnemonic, password: Option<&str>) -> PyResult<[u8; 32]> {
+    let big_seed =
+        substrate_bip39::seed_from_entropy(&mnemonic.to_entropy(), password.unwrap_or(""))
+            .map_err(|_| {
+                PyErr::new::<pyo3::exceptions::PyValueError, _>("Invalid mnemonic entropy")
+            })?;
+    let mut seed = [0u8; 32];
+    seed.copy_from_slice(&big_seed[..32]);
+    Ok(seed)
+}
+
+/// Return the root 32-byte seed of a secret URI, before any junctions are applied.
+///
+/// Like `sp_core`, a 0x-prefixed phrase is used as the seed directly and the password is ignored.
+fn uri_seed(uri: &SecretUri) -> PyResult<[u8; 32]> {
+    let phrase = uri.phrase.expose_secret();
+    if let Some(hex_str) = phrase.strip_prefix("0x") {
+        let mut seed = [0u8; 32];
+        hex::decode_to_slice(hex_str, &mut seed).map_err(|e| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid hex seed: {}", e))
+        })?;
+        Ok(seed)
+    } else {
+        let mnemonic = Mnemonic::parse(phrase.as_str()).map_err(|e| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid mnemonic: {}", e))
+        })?;
+        let password = uri.password.as_ref().map(|p| p.expose_secret().as_str());
+        phrase_seed(&mnemonic, password)
+    }
+}
+
+/// The signing context Substrate uses for sr25519 signatures.
+const SR25519_SIGNING_CONTEXT: &[u8] = b"substrate";
+
+/// An sr25519 keypair.
+///
+/// Keys are created and signed by `subxt_signer`. polkadot-js keystores hold the expanded secret
+/// key rather than a seed, which `subxt_signer` can't load, so keys loaded from a keystore are
+/// signed with schnorrkel directly, the same way. `subxt_signer` doesn't expose the secret keys of
+/// derived keypairs either, so the derivation is repeated with schnorrkel to keep them.
+#[derive(Clone)]
+enum Sr25519Keypair {
+    /// A `subxt_signer` keypair and its secret key in keystore format.
+    Signer(STKeypair, [u8; 64]),
+    /// A keypair loaded from a keystore.
+    Keystore(schnorrkel::Keypair),
+}
+
+impl Sr25519Keypair {
+    fn from_seed(seed: [u8; 32]) -> PyResult<Self> {
+        let keypair = STKeypair::from_secret_key(seed)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+        let secret_key = schnorrkel::MiniSecretKey::from_bytes(&seed)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?
+            .expand(ExpansionMode::Ed25519);
+        Ok(Sr25519Keypair::Signer(
+            keypair,
+            secret_key.to_ed25519_bytes(),
+        ))
+    }
+
+    /// Derive a keypair from a secret URI, the same way as Substrate's `sp_core::sr25519::Pair`.
+    fn from_uri(uri: &SecretUri) -> PyResult<Self> {
+        let keypair = STKeypair::from_uri(uri)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+        let mut secret_key = schnorrkel::MiniSecretKey::from_bytes(&uri_seed(uri)?)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?
+            .expand(ExpansionMode::Ed25519);
+        for junction in &uri.junctions {
+            secret_key = match *junction {
+                DeriveJunction::Soft(chain_code) => {
+                    secret_key.derived_key_simple(ChainCode(chain_code), b"").0
+                }
+                DeriveJunction::Hard(chain_code) => secret_key
+                    .hard_derive_mini_secret_key(Some(ChainCode(chain_code)), b"")
+                    .0
+                    .expand(ExpansionMode::Ed25519),
+            };
+        }
+        Ok(Sr25519Keypair::Signer(
+            keypair,
+            secret_key.to_ed25519_bytes(),
+        ))
+    }
+
+    /// Load a keypair from the 64-byte "ed25519 format" secret key used by polkadot-js.
+    fn from_keystore(secret_key: &[u8]) -> PyResult<Self> {
+        let secret_key = schnorrkel::SecretKey::from_ed25519_bytes(secret_key)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+        Ok(Sr25519Keypair::Keystore(secret_key.to_keypair()))
+    }
+
+    fn public_key(&self) -> [u8; 32] {
+        match self {
+            Sr25519Keypair::Signer(keypair, _) => keypair.public_key().0,
+            Sr25519Keypair::Keystore(keypair) => keypair.public.to_bytes(),
+        }
+    }
+
+    /// The 64-byte "ed25519 format" secret key used by polkadot-js.
+    fn secret_key(&self) -> [u8; 64] {
+        match self {
+            Sr25519Keypair::Signer(_, secret_key) => *secret_key,
+            Sr25519Keypair::Keystore(keypair) => keypair.secret.to_ed25519_bytes(),
+        }
+    }
+
+    fn sign(&self, message: &[u8]) -> [u8; 64] {
+        match self {
+            Sr25519Keypair::Signer(keypair, _) => keypair.sign(message).0,
+            Sr25519Keypair::Keystore(keypair) => {
+                let context = schnorrkel::signing_context(SR25519_SIGNING_CONTEXT);
+                keypair.sign(context.bytes(message)).to_bytes()
+            }
+        }
+    }
+}
+
+/// An ed25519 keypair, derived the same way as Substrate's `sp_core::ed25519::Pair`.
+#[derive(Clone)]
+struct Ed25519Keypair {
+    seed: [u8; 32],
+    signing_key: ed25519_dalek::SigningKey,
+}
+
+impl Ed25519Keypair {
+    fn from_seed(seed: [u8; 32]) -> Self {
+        Ed25519Keypair {
+            seed,
+            signing_key: ed25519_dalek::SigningKey::from_bytes(&seed),
+        }
+    }
+
+    /// Derive a child key. Like Substrate, ed25519 only supports hard junctions.
+    fn derive<Js: IntoIterator<Item = DeriveJunction>>(&self, junctions: Js) -> PyResult<Self> {
+        let mut acc = self.seed;
+        for junction in junctions {
+            match junction {
+                DeriveJunction::Soft(_) => {
+                    return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                        "Soft junctions are not supported for ed25519 keys",
+                    ))
+                }
+                DeriveJunction::Hard(junction_bytes) => {
+                    acc = ("Ed25519HDKD", acc, junction_bytes).using_encoded(blake2_256);
+                }
+            }
+        }
+        Ok(Self::from_seed(acc))
+    }
+
+    fn public_key(&self) -> [u8; 32] {
+        self.signing_key.verifying_key().to_bytes()
+    }
+
+    /// The 64-byte secret key used by polkadot-js: the seed followed by the public key.
+    fn secret_key(&self) -> [u8; 64] {
+        self.signing_key.to_keypair_bytes()
+    }
+
+    fn sign(&self, message: &[u8]) -> [u8; 64] {
+        ed25519_dalek::Signer::sign(&self.signing_key, message).to_bytes()
+    }
+}
+
+/// An ECDSA (secp256k1) keypair, signed by `subxt_signer`.
+#[derive(Clone)]
+struct EcdsaKeypair {
+    keypair: subxt_signer::ecdsa::Keypair,
+    secret_key: [u8; 32],
+}
+
+impl EcdsaKeypair {
+    fn from_seed(seed: [u8; 32]) -> PyResult<Self> {
+        let uri = SecretUri::from_str(&format!("0x{}", hex::encode(seed)))
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+        let keypair = subxt_signer::ecdsa::Keypair::from_uri(&uri)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+        Ok(EcdsaKeypair {
+            keypair,
+            secret_key: seed,
+        })
+    }
+
+    /// Derive a keypair from a secret URI, the same way as Substrate's `sp_core::ecdsa::Pair`.
+    /// Like Substrate, ECDSA only supports hard junctions.
+    fn from_uri(uri: &SecretUri) -> PyResult<Self> {
+        let mut acc = uri_seed(uri)?;
+        for junction in &uri.junctions {
+            match junction {
+                DeriveJunction::Soft(_) => {
+                    return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                        "Soft junctions are not supported for ECDSA keys",
+                    ))
+                }
+                DeriveJunction::Hard(junction_bytes) => {
+                    acc = ("Secp256k1HDKD", acc, junction_bytes).using_encoded(blake2_256);
+                }
+            }
+        }
+        Self::from_seed(acc)
+    }
+
+    fn public_key(&self) -> [u8; 33] {
+        self.keypair.public_key().0
+    }
+
+    fn secret_key(&self) -> [u8; 32] {
+        self.secret_key
+    }
+
+    /// Sign the blake2-256 hash of a message, producing a 65-byte recoverable signature.
+    fn sign(&self, message: &[u8]) -> [u8; 65] {
+        self.keypair.sign(message).0
+    }
+}
+
+/// An Ethereum-style secp256k1 keypair, as used by EVM parachains with 20-byte account ids.
+///
+/// Unlike Substrate ECDSA keys, the account id is the last 20 bytes of the keccak-256 hash of the
+/// uncompressed public key and payloads are hashed with keccak-256 before signing.
+#[derive(Clone)]
+struct EthKeypair {
+    secret_key: SecretKey,
+}
+
+impl EthKeypair {
+    fn from_secret_key(secret_key: [u8; 32]) -> PyResult<Self> {
+        let secret_key = SecretKey::from_slice(&secret_key)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+        Ok(EthKeypair { secret_key })
+    }
+
+    /// Derive the account at the BIP-44 path `m/44'/60'/0'/0/{index}`, like MetaMask and Moonbeam.
+    fn from_phrase(mnemonic: &Mnemonic, password: Option<&str>, index: u32) -> PyResult<Self> {
+        const HARDENED: u32 = 0x8000_0000;
+        let to_py_err =
+            |e: secp256k1::Error| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string());
+
+        let seed = mnemonic.to_seed_normalized(password.unwrap_or(""));
+        let master = hmac_sha512(b"Bitcoin seed", &seed);
+        let mut secret_key = SecretKey::from_slice(&master[..32]).map_err(to_py_err)?;
+        let mut chain_code = [0u8; 32];
+        chain_code.copy_from_slice(&master[32..]);
+
+        for child in [44 | HARDENED, 60 | HARDENED, HARDENED, 0, index] {
+            let mut data = Vec::with_capacity(37);
+            if child & HARDENED != 0 {
+                data.push(0);
+                data.extend_from_slice(&secret_key.secret_bytes());
+            } else {
+                data.extend_from_slice(&secret_key.public_key(SECP256K1).serialize());
+            }
+            data.extend_from_slice(&child.to_be_bytes());
+
+            let derived = hmac_sha512(&chain_code, &data);
+            let mut tweak = [0u8; 32];
+            tweak.copy_from_slice(&derived[..32]);
+            let tweak = Scalar::from_be_bytes(tweak)
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+            secret_key = secret_key.add_tweak(&tweak).map_err(to_py_err)?;
+            chain_code.copy_from_slice(&derived[32..]);
+        }
+        Ok(EthKeypair { secret_key })
+    }
+
+    fn public_key(&self) -> [u8; 33] {
+        self.secret_key.public_key(SECP256K1).serialize()
+    }
+
+    fn account_id(&self) -> [u8; 20] {
+        let uncompressed = self
+            .secret_key
+            .public_key(SECP256K1)
+            .serialize_uncompressed();
+        let mut account_id = [0u8; 20];
+        account_id.copy_from_slice(&keccak_256(&uncompressed[1..])[12..]);
+        account_id
+    }
+
+    fn secret_key(&self) -> [u8; 32] {
+        self.secret_key.secret_bytes()
+    }
+
+    /// Sign the keccak-256 hash of a message, producing a 65-byte recoverable signature.
+    fn sign(&self, message: &[u8]) -> [u8; 65] {
+        secp256k1_sign(&self.secret_key, keccak_256(message))
+    }
+}
+
+/// Sign a 32-byte message hash, returning the signature followed by the recovery id.
+fn secp256k1_sign(secret_key: &SecretKey, message_hash: [u8; 32]) -> [u8; 65] {
+    let message = Message::from_digest_slice(&message_hash).expect("Message is 32 bytes; qed");
+    let (recovery_id, signature) = SECP256K1
+        .sign_ecdsa_recoverable(&message, secret_key)
+        .serialize_compact();
+    let mut signature_bytes = [0u8; 65];
+    signature_bytes[..64].copy_from_slice(&signature);
+    signature_bytes[64] = recovery_id.to_i32() as u8;
+    signature_bytes
+}
+
+/// Recover the secp256k1 public key from a 65-byte recoverable signature over a message hash.
+fn secp256k1_recover(signature: &[u8], message_hash: [u8; 32]) -> Option<secp256k1::PublicKey> {
+    // Accept both raw (0/1) and Ethereum-style (27/28) recovery ids
+    let recovery_id = match signature[64] {
+        v @ 27..=28 => v - 27,
+        v => v,
+    };
+    let recovery_id = RecoveryId::from_i32(recovery_id as i32).ok()?;
+    let signature = RecoverableSignature::from_compact(&signature[..64], recovery_id).ok()?;
+    let message = Message::from_digest_slice(&message_hash).ok()?;
+    SECP256K1.recover_ecdsa(&message, &signature).ok()
+}
+
+/// The concrete keypair wrapped by the Keypair class.
+#[derive(Clone)]
+enum KeypairKind {
+    Sr25519(Sr25519Keypair),
+    Ed25519(Ed25519Keypair),
+    Ecdsa(EcdsaKeypair),
+    Ethereum(EthKeypair),
+}
+
+/// The runtime configuration used by SubxtClient.
+///
+/// This is the same as `PolkadotConfig`, except that account ids, addresses and signatures can
+/// either be the usual Substrate types or the Ethereum-style ones (20-byte account ids and
+/// keccak/secp256k1 signatures) used by EVM parachains such as Moonbeam. Which one is used is
+/// decided by the signing Keypair, and each variant encodes exactly like the type it wraps.
+///
+/// Chain-specific transaction extensions can also be given, see `ChainExtrinsicParams`.
+enum ChainConfig {}
+
+impl Config for ChainConfig {
+    type Hash = H256;
+    type AccountId = ChainAccountId;
+    type Address = ChainAddress;
+    type Signature = ChainSignature;
+    type Hasher = BlakeTwo256;
+    type Header = SubstrateHeader<u32, BlakeTwo256>;
+    type ExtrinsicParams = ChainExtrinsicParams;
+    type AssetId = u32;
+}
+
+/// An account id: either a Substrate `AccountId32` or an Ethereum-style `AccountId20`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum ChainAccountId {
+    Id32(AccountId32),
+    Id20([u8; 20]),
+}
+
+/// Serialize as the node's RPC methods expect: an SS58 address, or a hex string for `AccountId20`.
+impl serde::Serialize for ChainAccountId {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            ChainAccountId::Id32(id) => serde::Serialize::serialize(id, serializer),
+            ChainAccountId::Id20(id) => serializer.serialize_str(&format!("0x{}", hex::encode(id))),
+        }
+    }
+}
+
+impl Encode for ChainAccountId {
+    fn size_hint(&self) -> usize {
+        match self {
+            ChainAccountId::Id32(id) => id.size_hint(),
+            ChainAccountId::Id20(id) => id.size_hint(),
+        }
+    }
+
+    fn encode_to<O: Output + ?Sized>(&self, dest: &mut O) {
+        match self {
+            ChainAccountId::Id32(id) => id.encode_to(dest),
+            ChainAccountId::Id20(id) => id.encode_to(dest),
+        }
+    }
+}
+
+/// A transaction address: a `MultiAddress` for Substrate chains, or the bare `AccountId20` that
+/// Ethereum-style runtimes use as their address type.
+#[derive(Clone, Debug)]
+enum ChainAddress {
+    Multi(MultiAddress<AccountId32, ()>),
+    Id20([u8; 20]),
+}
+
+impl From<ChainAccountId> for ChainAddress {
+    fn from(account_id: ChainAccountId) -> Self {
+        match account_id {
+            ChainAccountId::Id32(id) => ChainAddress::Multi(MultiAddress::Id(id)),
+            ChainAccountId::Id20(id) => ChainAddress::Id20(id),
+        }
+    }
+}
+
+impl Encode for ChainAddress {
+    fn size_hint(&self) -> usize {
+        match self {
+            ChainAddress::Multi(address) => address.size_hint(),
+            ChainAddress::Id20(id) => id.size_hint(),
+        }
+    }
+
+    fn encode_to<O: Output + ?Sized>(&self, dest: &mut O) {
+        match self {
+            ChainAddress::Multi(address) => address.encode_to(dest),
+            ChainAddress::Id20(id) => id.encode_to(dest),
+        }
+    }
+}
+
+/// A transaction signature: a `MultiSignature` for Substrate chains, or the raw 65-byte
+/// `EthereumSignature` used by Ethereum-style runtimes.
+#[derive(Clone, Debug)]
+enum ChainSignature {
+    Multi(MultiSignature),
+    Ethereum([u8; 65]),
+}
+
+impl Encode for ChainSignature {
+    fn size_hint(&self) -> usize {
+        match self {
+            ChainSignature::Multi(signature) => signature.size_hint(),
+            ChainSignature::Ethereum(signature) => signature.size_hint(),
+        }
+    }
+
+    fn encode_to<O: Output + ?Sized>(&self, dest: &mut O) {
+        match self {
+            ChainSignature::Multi(signature) => signature.encode_to(dest),
+            ChainSignature::Ethereum(signature) => signature.encode_to(dest),
+        }
+    }
+}
+
 /// A Keypair used for signing transactions.
 ///
-/// This class wraps a sr25519 keypair and provides methods to create a keypair from a secret key.
+/// This class wraps a sr25519, ed25519, ECDSA (secp256k1) or Ethereum keypair and provides methods
+/// to create a keypair from a secret key, a BIP-39 mnemonic phrase or a Substrate secret URI. The
+/// scheme is chosen with the `scheme` argument and defaults to "sr25519".
 #[pyclass]
 #[derive(Clone)]
 struct Keypair {
-    keypair: STKeypair,
+    keypair: KeypairKind,
 }
 
 #[pymethods]
@@ -35,6 +600,7 @@ impl Keypair {
     ///
     /// Args:
     ///     secret_key (str): A 64-character hexadecimal string representing the secret key.
+    ///     scheme (str, optional): "sr25519" (default), "ed25519", "ecdsa" or "ethereum".
     ///
     /// Returns:
     ///     Keypair: A new Keypair instance.
@@ -42,7 +608,9 @@ impl Keypair {
     /// Raises:
     ///     ValueError: If the secret key is not 64 hex characters long or invalid.
     #[staticmethod]
-    fn from_secret_key(_py: Python, secret_key: &str) -> PyResult<Self> {
+    #[pyo3(signature = (secret_key, scheme="sr25519"))]
+    fn from_secret_key(_py: Python, secret_key: &str, scheme: &str) -> PyResult<Self> {
+        let scheme = KeyScheme::parse(scheme)?;
         if secret_key.len() != 64 {
             return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
                 "Secret key must be 32 bytes (64 hex characters) long",
@@ -54,527 +622,4072 @@ impl Keypair {
             PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid hex string: {}", e))
         })?;
 
-        let keypair = STKeypair::from_secret_key(secret_key_bytes)
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+        let keypair = match scheme {
+            KeyScheme::Sr25519 => {
+                KeypairKind::Sr25519(Sr25519Keypair::from_seed(secret_key_bytes)?)
+            }
+            KeyScheme::Ed25519 => KeypairKind::Ed25519(Ed25519Keypair::from_seed(secret_key_bytes)),
+            KeyScheme::Ecdsa => KeypairKind::Ecdsa(EcdsaKeypair::from_seed(secret_key_bytes)?),
+            KeyScheme::Ethereum => {
+                KeypairKind::Ethereum(EthKeypair::from_secret_key(secret_key_bytes)?)
+            }
+        };
         Ok(Keypair { keypair })
     }
-}
 
-impl<T: Config> SignerT<T> for Keypair
-where
-    T::AccountId: From<PublicKey>,
-    T::Address: From<PublicKey>,
-    T::Signature: From<Signature>,
-{
-    fn account_id(&self) -> T::AccountId {
-        self.keypair.public_key().into()
-    }
+    /// Create a new Keypair from a BIP-39 mnemonic phrase.
+    ///
+    /// Ethereum keys are derived at the standard `m/44'/60'/0'/0/0` path.
+    ///
+    /// Args:
+    ///     mnemonic (str): A BIP-39 mnemonic phrase, e.g. 12 or 24 English words.
+    ///     password (str, optional): An optional password used when deriving the seed.
+    ///     scheme (str, optional): "sr25519" (default), "ed25519", "ecdsa" or "ethereum".
+    ///
+    /// Returns:
+    ///     Keypair: A new Keypair instance.
+    ///
+    /// Raises:
+    ///     ValueError: If the phrase is not a valid BIP-39 mnemonic.
+    #[staticmethod]
+    #[pyo3(signature = (mnemonic, password=None, scheme="sr25519"))]
+    fn from_phrase(
+        _py: Python,
+        mnemonic: &str,
+        password: Option<&str>,
+        scheme: &str,
+    ) -> PyResult<Self> {
+        let scheme = KeyScheme::parse(scheme)?;
+        let mnemonic = Mnemonic::parse(mnemonic).map_err(|e| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid mnemonic: {}", e))
+        })?;
 
-    fn address(&self) -> T::Address {
-        self.keypair.public_key().into()
+        let keypair = match scheme {
+            KeyScheme::Sr25519 => KeypairKind::Sr25519(Sr25519Keypair::from_seed(phrase_seed(
+                &mnemonic, password,
+            )?)?),
+            KeyScheme::Ed25519 => {
+                KeypairKind::Ed25519(Ed25519Keypair::from_seed(phrase_seed(&mnemonic, password)?))
+            }
+            KeyScheme::Ecdsa => {
+                KeypairKind::Ecdsa(EcdsaKeypair::from_seed(phrase_seed(&mnemonic, password)?)?)
+            }
+            KeyScheme::Ethereum => {
+                KeypairKind::Ethereum(EthKeypair::from_phrase(&mnemonic, password, 0)?)
+            }
+        };
+        Ok(Keypair { keypair })
     }
 
-    fn sign(&self, signer_payload: &[u8]) -> T::Signature {
-        self.keypair.sign(signer_payload).into()
+    /// Create a new Keypair from a Substrate secret URI.
+    ///
+    /// The URI consists of a mnemonic phrase or a 0x-prefixed hex seed, followed by any number
+    /// of hard (`//`) or soft (`/`) derivation junctions and an optional `///password`. Soft
+    /// junctions are only supported by sr25519 keys, and Ethereum keys cannot be created from
+    /// a secret URI.
+    ///
+    /// Args:
+    ///     uri (str): A secret URI such as "//Alice", "//Alice//stash" or "<phrase>//0///pw".
+    ///     scheme (str, optional): "sr25519" (default), "ed25519", "ecdsa" or "ethereum".
+    ///
+    /// Returns:
+    ///     Keypair: A new Keypair instance.
+    ///
+    /// Raises:
+    ///     ValueError: If the URI cannot be parsed or the derivation fails.
+    #[staticmethod]
+    #[pyo3(signature = (uri, scheme="sr25519"))]
+    fn from_uri(_py: Python, uri: &str, scheme: &str) -> PyResult<Self> {
+        let scheme = KeyScheme::parse(scheme)?;
+        let uri = SecretUri::from_str(uri).map_err(|e| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid secret URI: {}", e))
+        })?;
+
+        let keypair = match scheme {
+            KeyScheme::Sr25519 => KeypairKind::Sr25519(Sr25519Keypair::from_uri(&uri)?),
+            KeyScheme::Ed25519 => KeypairKind::Ed25519(
+                Ed25519Keypair::from_seed(uri_seed(&uri)?).derive(uri.junctions.iter().copied())?,
+            ),
+            KeyScheme::Ecdsa => KeypairKind::Ecdsa(EcdsaKeypair::from_uri(&uri)?),
+            KeyScheme::Ethereum => {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "Ethereum keys use BIP-44 derivation; use from_phrase or from_secret_key",
+                ))
+            }
+        };
+        Ok(Keypair { keypair })
     }
-}
 
-/// A subscription to new blocks on the blockchain.
-///
-/// This class provides an asynchronous iterator over new blocks as they are finalized.
-///
-/// .. code-block:: python
-///
-///     async for block in subscription:
-///         print(block)
-#[pyclass]
-struct BlockSubscription {
-    blocks_stream: Arc<
-        tokio::sync::Mutex<StreamOfResults<Block<PolkadotConfig, OnlineClient<PolkadotConfig>>>>,
-    >,
-}
+    /// Return one of the well-known development accounts.
+    ///
+    /// Args:
+    ///     name (str): The account name, one of "alice", "bob", "charlie", "dave", "eve",
+    ///         "ferdie", "one" or "two" (case-insensitive). Ethereum dev accounts use the
+    ///         Moonbeam names "alith", "baltathar", "charleth", "dorothy", "ethan" and "faith".
+    ///     scheme (str, optional): "sr25519" (default), "ed25519", "ecdsa" or "ethereum".
+    ///
+    /// Returns:
+    ///     Keypair: The development account's keypair.
+    ///
+    /// Raises:
+    ///     ValueError: If the name is not a known development account.
+    #[staticmethod]
+    #[pyo3(signature = (name, scheme="sr25519"))]
+    fn dev(py: Python, name: &str, scheme: &str) -> PyResult<Self> {
+        if let KeyScheme::Ethereum = KeyScheme::parse(scheme)? {
+            let index = match name.to_lowercase().as_str() {
+                "alith" => 0,
+                "baltathar" => 1,
+                "charleth" => 2,
+                "dorothy" => 3,
+                "ethan" => 4,
+                "faith" => 5,
+                _ => {
+                    return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                        "Unknown dev account: {}",
+                        name
+                    )))
+                }
+            };
+            let mnemonic = Mnemonic::parse(DEV_PHRASE).expect("DEV_PHRASE is a valid mnemonic");
+            let keypair = KeypairKind::Ethereum(EthKeypair::from_phrase(&mnemonic, None, index)?);
+            return Ok(Keypair { keypair });
+        }
 
-#[pymethods]
-impl BlockSubscription {
-    /// Return the asynchronous iterator object.
-    fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
-        slf
+        let junction = match name.to_lowercase().as_str() {
+            "alice" => "Alice",
+            "bob" => "Bob",
+            "charlie" => "Charlie",
+            "dave" => "Dave",
+            "eve" => "Eve",
+            "ferdie" => "Ferdie",
+            "one" => "One",
+            "two" => "Two",
+            _ => {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Unknown dev account: {}",
+                    name
+                )))
+            }
+        };
+        Keypair::from_uri(py, &format!("//{}", junction), scheme)
     }
 
-    /// Return the next block from the subscription.
+    /// Create a new Keypair from a polkadot-js JSON keystore.
     ///
-    /// Yields:
-    ///     dict: A dictionary containing the block number, block hash, and a list of extrinsics.
+    /// This loads the encrypted JSON files exported by polkadot-js apps and the polkadot{.js}
+    /// extension. The key type is read from the keystore itself.
+    ///
+    /// Args:
+    ///     json_str (str): The contents of the JSON keystore.
+    ///     password (str): The password the keystore was exported with.
+    ///
+    /// Returns:
+    ///     Keypair: A new Keypair instance.
     ///
     /// Raises:
-    ///     StopAsyncIteration: When no more blocks are available.
-    fn __anext__<'a>(&self, py: Python<'a>) -> PyResult<Option<PyObject>> {
-        let blocks_stream = self.blocks_stream.clone();
-        let future = future_into_py(py, async move {
-            let mut blocks_stream = blocks_stream.lock().await;
-            match blocks_stream.next().await {
-                Some(block_result) => {
-                    let block = block_result.map_err(|e| {
-                        PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string())
-                    })?;
+    ///     ValueError: If the keystore is malformed, uses scrypt parameters other than the
+    ///         polkadot-js ones (N = 2^15, p = 1, r = 8), or the password is wrong.
+    #[staticmethod]
+    fn from_json(_py: Python, json_str: &str, password: &str) -> PyResult<Self> {
+        let invalid = |msg: &str| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid keystore: {}", msg))
+        };
+        let json: serde_json::Value =
+            serde_json::from_str(json_str).map_err(|e| invalid(&e.to_string()))?;
+        let encoded = json["encoded"]
+            .as_str()
+            .ok_or_else(|| invalid("missing `encoded`"))?;
+        let encoded = BASE64
+            .decode(encoded)
+            .map_err(|e| invalid(&e.to_string()))?;
 
-                    // Get block number and hash
-                    let block_number = block.header().number;
-                    let block_hash = block.hash();
+        let encoding = &json["encoding"];
+        let scheme = encoding["content"][1]
+            .as_str()
+            .ok_or_else(|| invalid("missing key type in `encoding.content`"))?;
+        let scheme = KeyScheme::parse(scheme)?;
+        // `encoding.type` is an array in v2/v3 keystores and a plain string in older ones
+        let encoding_types: Vec<&str> = match &encoding["type"] {
+            serde_json::Value::Array(types) => types.iter().filter_map(|t| t.as_str()).collect(),
+            serde_json::Value::String(t) => vec![t.as_str()],
+            _ => vec![],
+        };
+        let is_scrypt = encoding_types.contains(&"scrypt");
+        let plaintext = if encoding_types.contains(&"xsalsa20-poly1305") {
+            keystore_decrypt(&encoded, password, is_scrypt)?
+        } else {
+            encoded
+        };
 
-                    // Get the extrinsics
-                    let extrinsics = block.extrinsics().await.map_err(|e| {
-                        PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string())
-                    })?;
+        let secret_len = match scheme {
+            KeyScheme::Ecdsa | KeyScheme::Ethereum => 32,
+            KeyScheme::Sr25519 | KeyScheme::Ed25519 => 64,
+        };
+        let (secret_key, public_key) = pkcs8_decode(&plaintext, secret_len)?;
+        let keypair = match scheme {
+            KeyScheme::Sr25519 => KeypairKind::Sr25519(Sr25519Keypair::from_keystore(secret_key)?),
+            KeyScheme::Ed25519 => KeypairKind::Ed25519(Ed25519Keypair::from_seed(
+                secret_key[..32].try_into().expect("32 bytes; qed"),
+            )),
+            KeyScheme::Ecdsa => KeypairKind::Ecdsa(EcdsaKeypair::from_seed(
+                secret_key.try_into().expect("32 bytes; qed"),
+            )?),
+            KeyScheme::Ethereum => KeypairKind::Ethereum(EthKeypair::from_secret_key(
+                secret_key.try_into().expect("32 bytes; qed"),
+            )?),
+        };
 
-                    let mut extrinsics_info = vec![];
+        let keypair = Keypair { keypair };
+        if keypair_public_key(&keypair.keypair) != public_key {
+            return Err(invalid("public key does not match the secret key"));
+        }
+        Ok(keypair)
+    }
 
-                    for ext_result in extrinsics.iter() {
-                        let ext = ext_result.map_err(|e| {
-                            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string())
-                        })?;
+    /// The signature scheme of this keypair: "sr25519", "ed25519", "ecdsa" or "ethereum".
+    #[getter]
+    fn scheme(&self) -> &'static str {
+        match self.keypair {
+            KeypairKind::Sr25519(_) => "sr25519",
+            KeypairKind::Ed25519(_) => "ed25519",
+            KeypairKind::Ecdsa(_) => "ecdsa",
+            KeypairKind::Ethereum(_) => "ethereum",
+        }
+    }
 
-                        // Get signed extensions
-                        let signed_extensions = match ext.signed_extensions() {
-                            Some(signed_extensions) => signed_extensions,
-                            None => continue, // Skip unsigned extrinsics
-                        };
+    /// The public key of this keypair.
+    ///
+    /// This is 32 bytes for sr25519 and ed25519 keys, and the 33-byte compressed public key
+    /// for ECDSA and Ethereum keys.
+    #[getter]
+    fn public_key<'py>(&self, py: Python<'py>) -> &'py PyBytes {
+        PyBytes::new(py, &keypair_public_key(&self.keypair))
+    }
 
-                        // Get metadata
-                        let meta = ext.extrinsic_metadata().map_err(|e| {
-                            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string())
-                        })?;
-                        let pallet_name = meta.pallet.name();
-                        let call_name = &meta.variant.name;
+    /// The on-chain account id of this keypair as a hexadecimal string.
+    ///
+    /// This is the 32-byte AccountId32 (blake2-256 of the public key for ECDSA keys), or the
+    /// 20-byte AccountId20 for Ethereum keys, and can be passed directly in payloads and keys.
+    /// 40-character hex strings are only read as account ids on chains with 20-byte accounts.
+    #[getter(account_id)]
+    fn py_account_id(&self) -> String {
+        hex::encode(chain_account_id(&self.keypair).encode())
+    }
 
-                        // Get field values
-                        let fields = ext.field_values().map_err(|e| {
-                            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string())
-                        })?;
+    /// Return the SS58 address of this keypair.
+    ///
+    /// Args:
+    ///     prefix (int, optional): The SS58 network prefix, 42 (generic Substrate) by default.
+    ///         Use `SubxtClient.ss58_prefix()` to get the prefix of the connected chain.
+    ///
+    /// Returns:
+    ///     str: The SS58-encoded address.
+    ///
+    /// Raises:
+    ///     ValueError: If the prefix is out of range or the keypair is an Ethereum keypair.
+    #[pyo3(signature = (prefix=42))]
+    fn ss58_address(&self, prefix: u16) -> PyResult<String> {
+        match chain_account_id(&self.keypair) {
+            ChainAccountId::Id32(account_id) => ss58_encode(&account_id.0, prefix),
+            ChainAccountId::Id20(_) => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "Ethereum accounts do not have an SS58 address",
+            )),
+        }
+    }
 
-                        // Convert fields to Python object
-                        let py_fields = Python::with_gil(|py| composite_to_py_object(py, &fields))?;
+    /// Sign an arbitrary message.
+    ///
+    /// Args:
+    ///     message (bytes): The message to sign.
+    ///     wrap (bool, optional): Wrap the message in `<Bytes>...</Bytes>` before signing, as
+    ///         polkadot-js `signRaw` does. Defaults to False.
+    ///
+    /// Returns:
+    ///     bytes: The signature; 64 bytes for sr25519 and ed25519 keys, 65 bytes for ECDSA and
+    ///         Ethereum keys.
+    #[pyo3(name = "sign", signature = (message, wrap=false))]
+    fn py_sign<'py>(&self, py: Python<'py>, message: &[u8], wrap: bool) -> &'py PyBytes {
+        let message = if wrap {
+            wrap_bytes(message)
+        } else {
+            message.to_vec()
+        };
+        match &self.keypair {
+            KeypairKind::Sr25519(keypair) => PyBytes::new(py, &keypair.sign(&message)),
+            KeypairKind::Ed25519(keypair) => PyBytes::new(py, &keypair.sign(&message)),
+            KeypairKind::Ecdsa(keypair) => PyBytes::new(py, &keypair.sign(&message)),
+            KeypairKind::Ethereum(keypair) => PyBytes::new(py, &keypair.sign(&message)),
+        }
+    }
 
-                        // Collect signed extensions
-                        let py_signed_extensions = Python::with_gil(|py| -> PyResult<PyObject> {
-                            let mut ext_list = Vec::new();
-                            for se_result in signed_extensions.iter() {
-                                let se = match se_result {
-                                    Ok(se) => se,
-                                    Err(_) => continue,
-                                };
-                                let name = se.name();
-                                if ["CheckMortality", "CheckNonce", "ChargeTransactionPayment"]
-                                    .contains(&name)
-                                {
-                                    let value = match se.value() {
-                                        Ok(value) => value,
-                                        Err(_) => continue,
-                                    };
-                                    let value_py = decoded_value_to_py_object(py, &value)?;
-                                    let dict = PyDict::new(py);
-                                    dict.set_item("name", name)?;
-                                    dict.set_item("value", value_py)?;
-                                    ext_list.push(dict.to_object(py));
-                                }
-                            }
-                            Ok(PyList::new(py, ext_list).to_object(py))
-                        })?;
+    /// Export this keypair as an encrypted polkadot-js JSON keystore.
+    ///
+    /// The keystore uses the same v3 format (scrypt + xsalsa20-poly1305 encoded PKCS8) as
+    /// polkadot-js apps, so it can be imported there or loaded again with `Keypair.from_json`.
+    ///
+    /// Args:
+    ///     password (str): The password to encrypt the keystore with.
+    ///     name (str, optional): An account name to store in the keystore metadata.
+    ///
+    /// Returns:
+    ///     str: The JSON keystore.
+    ///
+    /// Raises:
+    ///     RuntimeError: If the keystore can't be encrypted.
+    #[pyo3(signature = (password, name=None))]
+    fn to_json(&self, password: &str, name: Option<&str>) -> PyResult<String> {
+        let plaintext = [
+            &PKCS8_HEADER[..],
+            &keystore_secret(&self.keypair),
+            &PKCS8_DIVIDER[..],
+            &keypair_public_key(&self.keypair),
+        ]
+        .concat();
+        let encoded = keystore_encrypt(&plaintext, password)?;
 
-                        // Collect extrinsic information
-                        let extrinsic_info = Python::with_gil(|py| -> PyResult<PyObject> {
-                            let dict = PyDict::new(py);
-                            dict.set_item("pallet", pallet_name)?;
-                            dict.set_item("call", call_name)?;
-                            dict.set_item("fields", py_fields)?;
-                            dict.set_item("signed_extensions", py_signed_extensions)?;
-                            Ok(dict.to_object(py))
-                        })?;
+        let address = match chain_account_id(&self.keypair) {
+            ChainAccountId::Id32(account_id) => ss58_encode(&account_id.0, 42)?,
+            ChainAccountId::Id20(account_id) => format!("0x{}", hex::encode(account_id)),
+        };
+        let when_created = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default();
+        let mut meta = serde_json::json!({ "whenCreated": when_created });
+        if let Some(name) = name {
+            meta["name"] = name.into();
+        }
 
-                        extrinsics_info.push(extrinsic_info);
+        let json = serde_json::json!({
+            "encoded": BASE64.encode(encoded),
+            "encoding": {
+                "content": ["pkcs8", self.scheme()],
+                "type": ["scrypt", "xsalsa20-poly1305"],
+                "version": "3",
+            },
+            "address": address,
+            "meta": meta,
+        });
+        Ok(json.to_string())
+    }
+}
+
+impl SignerT<ChainConfig> for Keypair {
+    fn account_id(&self) -> ChainAccountId {
+        chain_account_id(&self.keypair)
+    }
+
+    fn address(&self) -> ChainAddress {
+        chain_account_id(&self.keypair).into()
+    }
+
+    fn sign(&self, signer_payload: &[u8]) -> ChainSignature {
+        match &self.keypair {
+            KeypairKind::Sr25519(keypair) => {
+                ChainSignature::Multi(MultiSignature::Sr25519(keypair.sign(signer_payload)))
+            }
+            KeypairKind::Ed25519(keypair) => {
+                ChainSignature::Multi(MultiSignature::Ed25519(keypair.sign(signer_payload)))
+            }
+            KeypairKind::Ecdsa(keypair) => {
+                ChainSignature::Multi(MultiSignature::Ecdsa(keypair.sign(signer_payload)))
+            }
+            KeypairKind::Ethereum(keypair) => {
+                ChainSignature::Ethereum(keypair.sign(signer_payload))
+            }
+        }
+    }
+}
+
+/// A signer backed by an arbitrary Python object, e.g. a KMS or HSM client.
+///
+/// The object must provide `account_id()`, returning the signer's account id as bytes, a hex
+/// string or an SS58 address, and a synchronous `sign(payload: bytes) -> bytes`. An optional
+/// `scheme` attribute ("sr25519", "ed25519", "ecdsa" or "ethereum") tells us how to interpret
+/// the signature; it defaults to "ethereum" for 20-byte account ids and "sr25519" otherwise.
+struct ExternalSigner {
+    signer: PyObject,
+    account_id: ChainAccountId,
+    scheme: KeyScheme,
+    /// `Signer::sign` can't fail, so errors raised while signing are kept here until the
+    /// extrinsic has been built.
+    error: std::sync::Mutex<Option<PyErr>>,
+}
+
+impl ExternalSigner {
+    fn new(signer: &PyAny) -> PyResult<Self> {
+        // Accept both methods and properties, so a `Keypair` also satisfies the protocol
+        let attr = |name: &str| -> PyResult<&PyAny> {
+            let value = signer.getattr(name)?;
+            if value.is_callable() {
+                value.call0()
+            } else {
+                Ok(value)
+            }
+        };
+
+        let account_id = py_to_account_id(attr("account_id")?)?;
+        let scheme = if signer.hasattr("scheme")? {
+            KeyScheme::parse(attr("scheme")?.extract()?)?
+        } else {
+            KeyScheme::default_for(&account_id)
+        };
+        Ok(ExternalSigner {
+            signer: signer.into(),
+            account_id,
+            scheme,
+            error: std::sync::Mutex::new(None),
+        })
+    }
+
+    fn try_sign(&self, signer_payload: &[u8]) -> PyResult<ChainSignature> {
+        let signature: Vec<u8> = Python::with_gil(|py| {
+            self.signer
+                .call_method1(py, "sign", (PyBytes::new(py, signer_payload),))?
+                .extract(py)
+        })?;
+        self.scheme.signature(&signature)
+    }
+
+    /// Return the error raised by the last call to `sign`, if any.
+    fn take_error(&self) -> Option<PyErr> {
+        self.error
+            .lock()
+            .expect("lock is never poisoned; qed")
+            .take()
+    }
+}
+
+impl SignerT<ChainConfig> for ExternalSigner {
+    fn account_id(&self) -> ChainAccountId {
+        self.account_id.clone()
+    }
+
+    fn address(&self) -> ChainAddress {
+        self.account_id.clone().into()
+    }
+
+    fn sign(&self, signer_payload: &[u8]) -> ChainSignature {
+        self.try_sign(signer_payload).unwrap_or_else(|e| {
+            *self.error.lock().expect("lock is never poisoned; qed") = Some(e);
+            // Never submitted; `create_signed` checks for the error before returning
+            ChainSignature::Multi(MultiSignature::Sr25519([0; 64]))
+        })
+    }
+}
+
+/// The signer of a transaction: either a `Keypair` or an external Python signer.
+enum TxSigner {
+    Keypair(Keypair),
+    External(ExternalSigner),
+}
+
+impl TxSigner {
+    fn extract(signer: &PyAny) -> PyResult<Self> {
+        match signer.extract::<Keypair>() {
+            Ok(keypair) => Ok(TxSigner::Keypair(keypair)),
+            Err(_) => Ok(TxSigner::External(ExternalSigner::new(signer)?)),
+        }
+    }
+
+    /// Return the error raised by an external signer while signing, if any.
+    fn check_error(&self) -> PyResult<()> {
+        match self {
+            TxSigner::External(signer) => signer.take_error().map_or(Ok(()), Err),
+            TxSigner::Keypair(_) => Ok(()),
+        }
+    }
+}
+
+impl SignerT<ChainConfig> for TxSigner {
+    fn account_id(&self) -> ChainAccountId {
+        match self {
+            TxSigner::Keypair(keypair) => SignerT::account_id(keypair),
+            TxSigner::External(signer) => SignerT::account_id(signer),
+        }
+    }
+
+    fn address(&self) -> ChainAddress {
+        match self {
+            TxSigner::Keypair(keypair) => SignerT::address(keypair),
+            TxSigner::External(signer) => SignerT::address(signer),
+        }
+    }
+
+    fn sign(&self, signer_payload: &[u8]) -> ChainSignature {
+        match self {
+            TxSigner::Keypair(keypair) => SignerT::sign(keypair, signer_payload),
+            TxSigner::External(signer) => SignerT::sign(signer, signer_payload),
+        }
+    }
+}
+
+/// Transaction parameters, passed as keyword arguments to the methods that sign transactions.
+#[derive(Clone, Default)]
+struct TxOptions {
+    tip: u128,
+    tip_asset_id: Option<u32>,
+    mortality_blocks: Option<u64>,
+    nonce: Option<u64>,
+    extras: Vec<(String, Value)>,
+}
+
+impl TxOptions {
+    /// Parse the `tip`, `tip_asset_id`, `mortality_blocks`, `nonce` and `extras` keyword
+    /// arguments.
+    fn from_kwargs(kwargs: Option<&PyDict>, account_id20: bool) -> PyResult<Self> {
+        let mut options = TxOptions::default();
+        for (key, value) in kwargs.into_iter().flatten() {
+            match key.extract::<&str>()? {
+                "tip" => options.tip = value.extract()?,
+                "tip_asset_id" => options.tip_asset_id = value.extract()?,
+                "mortality_blocks" => options.mortality_blocks = value.extract()?,
+                "nonce" => options.nonce = value.extract()?,
+                "extras" => {
+                    for (name, extra) in value.downcast::<PyDict>()? {
+                        options.extras.push((
+                            name.extract()?,
+                            py_object_to_value(extra, AddressUse::Extrinsic, account_id20)?,
+                        ));
                     }
+                }
+                other => {
+                    return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(format!(
+                        "Unexpected transaction parameter: {}",
+                        other
+                    )))
+                }
+            }
+        }
+        Ok(options)
+    }
 
-                    // Create a Python dictionary with block info
-                    let py_block_info = Python::with_gil(|py| -> PyResult<PyObject> {
-                        let dict = PyDict::new(py);
-                        dict.set_item("block_number", block_number)?;
-                        dict.set_item("block_hash", format!("{:?}", block_hash))?;
-                        dict.set_item("extrinsics", PyList::new(py, extrinsics_info))?;
-                        Ok(dict.to_object(py))
-                    })?;
-                    Ok(Some(py_block_info))
+    /// Build the extrinsic params. Mortal transactions are checkpointed at the latest block.
+    async fn build(&self, api: &OnlineClient<ChainConfig>) -> PyResult<TxParams> {
+        let mut params = self.builder();
+        if let Some(mortality_blocks) = self.mortality_blocks {
+            let block =
+                api.blocks().at_latest().await.map_err(|e| {
+                    PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string())
+                })?;
+            params = params.mortal(block.header(), mortality_blocks);
+        }
+        Ok(TxParams {
+            substrate: params.build(),
+            extras: self.extras.clone(),
+        })
+    }
+
+    /// Build the extrinsic params without a node connection.
+    ///
+    /// The nonce must be given, and transactions are immortal since there's no block to
+    /// checkpoint a mortal transaction at.
+    fn build_offline(&self) -> PyResult<TxParams> {
+        if self.nonce.is_none() {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "A nonce is required to sign transactions offline",
+            ));
+        }
+        if self.mortality_blocks.is_some() {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "Mortal transactions can't be signed offline",
+            ));
+        }
+        Ok(TxParams {
+            substrate: self.builder().build(),
+            extras: self.extras.clone(),
+        })
+    }
+
+    fn builder(&self) -> Params<ChainConfig> {
+        let mut params = match self.tip_asset_id {
+            Some(asset_id) => Params::<ChainConfig>::new().tip_of(self.tip, asset_id),
+            None => Params::<ChainConfig>::new().tip(self.tip),
+        };
+        if let Some(nonce) = self.nonce {
+            params = params.nonce(nonce);
+        }
+        params
+    }
+}
+
+/// The extrinsic params passed to subxt when signing a transaction.
+struct TxParams {
+    substrate: <SubstrateExtrinsicParams<ChainConfig> as ExtrinsicParams<ChainConfig>>::Params,
+    extras: Vec<(String, Value)>,
+}
+
+impl RefineParams<ChainConfig> for TxParams {
+    fn refine(&mut self, data: &RefineParamsData<ChainConfig>) {
+        self.substrate.refine(data);
+    }
+}
+
+/// The transaction extensions used by `ChainConfig`.
+///
+/// These are the extensions in `SubstrateExtrinsicParams`, plus chain-specific ones such as
+/// Avail's `CheckAppId`, given by name in the `extras` transaction parameter. Their values are
+/// encoded against the types in the metadata's list of transaction extensions.
+struct ChainExtrinsicParams {
+    extensions: Vec<Box<dyn ExtrinsicParamsEncoder>>,
+}
+
+impl ExtrinsicParams<ChainConfig> for ChainExtrinsicParams {
+    type Params = TxParams;
+
+    fn new(
+        client: &ClientState<ChainConfig>,
+        params: Self::Params,
+    ) -> Result<Self, ExtrinsicParamsError> {
+        let metadata = &client.metadata;
+        let signed_extensions = metadata.extrinsic().signed_extensions();
+
+        for (identifier, _) in &params.extras {
+            if !signed_extensions
+                .iter()
+                .any(|extension| extension.identifier() == identifier)
+            {
+                return Err(ExtrinsicParamsError::Custom(Box::new(format!(
+                    "The chain has no {} transaction extension",
+                    identifier
+                ))));
+            }
+        }
+        let mut extras: HashMap<String, Value> = params.extras.into_iter().collect();
+        let mut encoders = Vec::new();
+        for extension in signed_extensions {
+            encoders.push(match extras.remove(extension.identifier()) {
+                Some(value) => Some(ExtraExtension::new(extension, value, metadata)?),
+                None => None,
+            });
+        }
+
+        let (spec_version, tx_version, nonce, genesis, mortality, asset_tip, tip, metadata_hash) =
+            params.substrate;
+        add_extension::<CheckSpecVersion>(client, &mut encoders, spec_version)?;
+        add_extension::<CheckTxVersion>(client, &mut encoders, tx_version)?;
+        add_extension::<CheckNonce>(client, &mut encoders, nonce)?;
+        add_extension::<CheckGenesis<ChainConfig>>(client, &mut encoders, genesis)?;
+        add_extension::<CheckMortality<ChainConfig>>(client, &mut encoders, mortality)?;
+        add_extension::<ChargeAssetTxPayment<ChainConfig>>(client, &mut encoders, asset_tip)?;
+        add_extension::<ChargeTransactionPayment>(client, &mut encoders, tip)?;
+        add_extension::<CheckMetadataHash>(client, &mut encoders, metadata_hash)?;
+
+        // As in subxt, extensions without any data needn't be known
+        let mut extensions = Vec::new();
+        for (extension, encoder) in signed_extensions.iter().zip(encoders) {
+            match encoder {
+                Some(encoder) => extensions.push(encoder),
+                None if is_type_empty(extension.extra_ty(), metadata)
+                    && is_type_empty(extension.additional_ty(), metadata) => {}
+                None => {
+                    return Err(ExtrinsicParamsError::UnknownSignedExtension(
+                        extension.identifier().to_owned(),
+                    ))
                 }
-                None => Err(PyErr::new::<pyo3::exceptions::PyStopAsyncIteration, _>(
-                    "No more blocks",
-                )),
             }
-        });
-        Ok(Some(future?.into()))
+        }
+        Ok(ChainExtrinsicParams { extensions })
     }
 }
 
-/// An asynchronous iterator over storage key-value pairs.
+impl ExtrinsicParamsEncoder for ChainExtrinsicParams {
+    fn encode_extra_to(&self, v: &mut Vec<u8>) {
+        for extension in &self.extensions {
+            extension.encode_extra_to(v);
+        }
+    }
+
+    fn encode_additional_to(&self, v: &mut Vec<u8>) {
+        for extension in &self.extensions {
+            extension.encode_additional_to(v);
+        }
+    }
+}
+
+/// A transaction extension given in the `extras` transaction parameter, already encoded.
+struct ExtraExtension(Vec<u8>);
+
+impl ExtraExtension {
+    fn new(
+        extension: &SignedExtensionMetadata,
+        value: Value,
+        metadata: &subxt::Metadata,
+    ) -> Result<Box<dyn ExtrinsicParamsEncoder>, ExtrinsicParamsError> {
+        if !is_type_empty(extension.additional_ty(), metadata) {
+            return Err(ExtrinsicParamsError::Custom(Box::new(format!(
+                "The {} transaction extension has additional signed data, so it can't be given \
+                 as an extra",
+                extension.identifier()
+            ))));
+        }
+        let mut extra = Vec::new();
+        value
+            .encode_as_type_to(extension.extra_ty(), metadata.types(), &mut extra)
+            .map_err(|e| ExtrinsicParamsError::Custom(Box::new(e)))?;
+        Ok(Box::new(ExtraExtension(extra)))
+    }
+}
+
+impl ExtrinsicParamsEncoder for ExtraExtension {
+    fn encode_extra_to(&self, v: &mut Vec<u8>) {
+        v.extend_from_slice(&self.0);
+    }
+}
+
+/// Use the extension `E` for the first matching extension in the metadata that isn't given
+/// yet, as subxt's `AnyOf` does.
+fn add_extension<E: SignedExtension<ChainConfig>>(
+    client: &ClientState<ChainConfig>,
+    encoders: &mut [Option<Box<dyn ExtrinsicParamsEncoder>>],
+    params: E::Params,
+) -> Result<(), ExtrinsicParamsError> {
+    let metadata = &client.metadata;
+    let signed_extensions = metadata.extrinsic().signed_extensions();
+    for (extension, encoder) in signed_extensions.iter().zip(encoders.iter_mut()) {
+        if encoder.is_none()
+            && E::matches(
+                extension.identifier(),
+                extension.extra_ty(),
+                metadata.types(),
+            )
+        {
+            *encoder = Some(Box::new(E::new(client, params)?));
+            return Ok(());
+        }
+    }
+    Ok(())
+}
+
+/// Whether a type encodes to no bytes at all, like `()` or `PhantomData`.
+fn is_type_empty(type_id: u32, metadata: &subxt::Metadata) -> bool {
+    match metadata.types().resolve(type_id).map(|ty| &ty.type_def) {
+        Some(TypeDef::Composite(composite)) => composite
+            .fields
+            .iter()
+            .all(|field| is_type_empty(field.ty.id, metadata)),
+        Some(TypeDef::Tuple(tuple)) => tuple
+            .fields
+            .iter()
+            .all(|field| is_type_empty(field.id, metadata)),
+        Some(TypeDef::Array(array)) => {
+            array.len == 0 || is_type_empty(array.type_param.id, metadata)
+        }
+        _ => false,
+    }
+}
+
+/// Hands out sequential nonces per account, so concurrent submissions through one client don't
+/// collide.
 ///
-/// This class allows you to iterate over storage entries retrieved from the blockchain.
-#[pyclass]
-struct StorageIterator {
-    results:
-        Arc<tokio::sync::Mutex<StreamOfResults<StorageKeyValuePair<DynamicAddress<Vec<Value>>>>>>,
+/// The next nonce is the larger of the account's next index from `system_accountNextIndex`, which
+/// counts transactions in the node's pool, and one past the last nonce used. Each account has its
+/// own lock, so waiting on the node for one account doesn't hold up the others. When a submission
+/// fails or a transaction is dropped or invalid, the account's next nonce is fetched again.
+struct NonceManager {
+    rpc: LegacyRpcMethods<ChainConfig>,
+    next_nonces: std::sync::Mutex<HashMap<ChainAccountId, Arc<tokio::sync::Mutex<Option<u64>>>>>,
 }
 
-#[pymethods]
-impl StorageIterator {
-    /// Return the asynchronous iterator object.
-    fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
-        slf
+impl NonceManager {
+    fn new(rpc: LegacyRpcMethods<ChainConfig>) -> Self {
+        NonceManager {
+            rpc,
+            next_nonces: Default::default(),
+        }
+    }
+
+    /// The next nonce of an account, if one has been used, behind the account's lock.
+    fn next_nonce(&self, account_id: &ChainAccountId) -> Arc<tokio::sync::Mutex<Option<u64>>> {
+        let mut next_nonces = self
+            .next_nonces
+            .lock()
+            .expect("no code panics while holding the lock; qed");
+        next_nonces.entry(account_id.clone()).or_default().clone()
+    }
+
+    async fn chain_nonce(&self, account_id: &ChainAccountId) -> PyResult<u64> {
+        self.rpc
+            .system_account_next_index(account_id)
+            .await
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
+    }
+
+    async fn next(&self, account_id: &ChainAccountId) -> PyResult<u64> {
+        let next_nonce = self.next_nonce(account_id);
+        let mut next_nonce = next_nonce.lock().await;
+        let chain_nonce = self.chain_nonce(account_id).await?;
+        let nonce = next_nonce.map_or(chain_nonce, |next_nonce| next_nonce.max(chain_nonce));
+        *next_nonce = Some(nonce + 1);
+        Ok(nonce)
+    }
+
+    /// Record a nonce that was given explicitly, so the nonces handed out afterwards follow it.
+    async fn advance(&self, account_id: &ChainAccountId, nonce: u64) {
+        let next_nonce = self.next_nonce(account_id);
+        let mut next_nonce = next_nonce.lock().await;
+        *next_nonce = Some(next_nonce.map_or(nonce + 1, |next_nonce| next_nonce.max(nonce + 1)));
+    }
+
+    /// Fetch the account's next nonce again, after a nonce went unused.
+    async fn resync(&self, account_id: &ChainAccountId) {
+        let next_nonce = self.next_nonce(account_id);
+        let mut next_nonce = next_nonce.lock().await;
+        // If the node can't be reached, the next transaction fetches the nonce instead
+        *next_nonce = self.chain_nonce(account_id).await.ok();
+    }
+}
+
+/// Build an extrinsic ready to be signed by `account_id`.
+///
+/// Unless a nonce is given in `options`, one is taken from the nonce manager and stored in
+/// `options`.
+async fn create_partial(
+    api: &OnlineClient<ChainConfig>,
+    nonces: &NonceManager,
+    tx_payload: &impl Payload,
+    account_id: &ChainAccountId,
+    options: &mut TxOptions,
+) -> PyResult<PartialExtrinsic<ChainConfig, OnlineClient<ChainConfig>>> {
+    let managed_nonce = options.nonce.is_none();
+    if managed_nonce {
+        options.nonce = Some(nonces.next(account_id).await?);
+    }
+
+    let result = async {
+        let tx_params = options.build(api).await?;
+        api.tx()
+            .create_partial_signed(tx_payload, account_id, tx_params)
+            .await
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
+    }
+    .await;
+    if result.is_err() && managed_nonce {
+        // The nonce was never used, so don't leave a gap
+        nonces.resync(account_id).await;
+    }
+    result
+}
+
+/// Build and sign an extrinsic, surfacing any error raised by an external signer.
+///
+/// Unless a nonce is given in `options`, one is taken from the nonce manager. A given nonce is
+/// recorded in the nonce manager instead.
+async fn create_signed(
+    api: &OnlineClient<ChainConfig>,
+    nonces: &NonceManager,
+    tx_payload: &impl Payload,
+    signer: &TxSigner,
+    options: &TxOptions,
+) -> PyResult<SubmittableExtrinsic<ChainConfig, OnlineClient<ChainConfig>>> {
+    let account_id = signer.account_id();
+    let mut options = options.clone();
+    let given_nonce = options.nonce;
+    let partial = create_partial(api, nonces, tx_payload, &account_id, &mut options).await?;
+    let extrinsic = partial.sign(signer);
+    if let Err(e) = signer.check_error() {
+        if given_nonce.is_none() {
+            nonces.resync(&account_id).await;
+        }
+        return Err(e);
+    }
+    if let Some(nonce) = given_nonce {
+        nonces.advance(&account_id, nonce).await;
+    }
+    Ok(extrinsic)
+}
+
+/// Build, sign and submit an extrinsic, returning its hash as a hexadecimal string.
+async fn submit_signed(
+    api: &OnlineClient<ChainConfig>,
+    nonces: &NonceManager,
+    tx_payload: &impl Payload,
+    signer: &TxSigner,
+    options: &TxOptions,
+) -> PyResult<String> {
+    let extrinsic = create_signed(api, nonces, tx_payload, signer, options).await?;
+    match extrinsic.submit().await {
+        Ok(hash) => Ok(format!("{:?}", hash)),
+        Err(e) => {
+            nonces.resync(&signer.account_id()).await;
+            Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+                e.to_string(),
+            ))
+        }
+    }
+}
+
+/// Build, sign and submit an extrinsic, returning a subscription to its status updates.
+async fn submit_and_watch(
+    api: &OnlineClient<ChainConfig>,
+    nonces: &NonceManager,
+    tx_payload: &impl Payload,
+    signer: &TxSigner,
+    options: &TxOptions,
+) -> PyResult<TxProgress<ChainConfig, OnlineClient<ChainConfig>>> {
+    let extrinsic = create_signed(api, nonces, tx_payload, signer, options).await?;
+    match extrinsic.submit_and_watch().await {
+        Ok(progress) => Ok(progress),
+        Err(e) => {
+            nonces.resync(&signer.account_id()).await;
+            Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+                e.to_string(),
+            ))
+        }
+    }
+}
+
+/// Submit an extrinsic wrapping another call, and wait for it to be finalized.
+///
+/// Envelopes like `Sudo.sudo` succeed even if the wrapped call fails, reporting its result in an
+/// event instead. If `inner_event` is given, the result in the outer envelope's event is checked,
+/// and a failed wrapped call is raised as a `DispatchError`. Envelopes without such an event, like
+/// `Utility.as_derivative`, fail the whole extrinsic instead.
+async fn submit_wrapped(
+    api: &OnlineClient<ChainConfig>,
+    nonces: &NonceManager,
+    tx_payload: &impl Payload,
+    signer: &TxSigner,
+    options: &TxOptions,
+    inner_event: Option<(&str, &str)>,
+) -> PyResult<ExtrinsicEvents<ChainConfig>> {
+    let progress = submit_and_watch(api, nonces, tx_payload, signer, options).await?;
+    let events = wait_for_success(progress, nonces, Some(&signer.account_id())).await?;
+    let (pallet_name, variant_name) = match inner_event {
+        Some(inner_event) => inner_event,
+        None => return Ok(events),
+    };
+
+    // The envelope emits its event after dispatching the wrapped call, so if that wraps another
+    // envelope of the same kind, e.g. `Sudo.sudo(Sudo.sudo(...))`, the outer one's event is last
+    let mut inner_result = None;
+    for event in events.iter() {
+        let event =
+            event.map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+        if event.pallet_name() == pallet_name && event.variant_name() == variant_name {
+            inner_result = Some(event.field_bytes().to_vec());
+        }
+    }
+    // The event's only field is a `DispatchResult`
+    if let Some([1, error @ ..]) = inner_result.as_deref() {
+        let error = subxt::error::DispatchError::decode_from(error, api.metadata())
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+        return Err(dispatch_error_to_py(&error));
+    }
+    Ok(events)
+}
+
+/// Fetch an account's on-chain nonce, for transactions built without the nonce manager.
+async fn account_nonce(
+    api: &OnlineClient<ChainConfig>,
+    account_id: &ChainAccountId,
+) -> PyResult<u64> {
+    api.tx()
+        .account_nonce(account_id)
+        .await
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
+}
+
+/// Build and sign an extrinsic for fee estimation or a dry run.
+///
+/// Unless a nonce is given in `options`, the account's on-chain nonce is used. The nonce manager
+/// isn't involved, since the transaction won't be submitted.
+async fn create_signed_for_query(
+    api: &OnlineClient<ChainConfig>,
+    tx_payload: &impl Payload,
+    signer: &TxSigner,
+    options: &TxOptions,
+) -> PyResult<SubmittableExtrinsic<ChainConfig, OnlineClient<ChainConfig>>> {
+    let account_id = signer.account_id();
+    let mut options = options.clone();
+    if options.nonce.is_none() {
+        options.nonce = Some(account_nonce(api, &account_id).await?);
+    }
+    let tx_params = options.build(api).await?;
+    let partial = api
+        .tx()
+        .create_partial_signed(tx_payload, &account_id, tx_params)
+        .await
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+    let extrinsic = partial.sign(signer);
+    signer.check_error()?;
+    Ok(extrinsic)
+}
+
+/// A runtime API call with pre-encoded arguments, decoded dynamically like `runtime_api_call`.
+///
+/// This is needed for runtime APIs taking an extrinsic, whose opaque encoding can't be built from
+/// a `Value`.
+struct RawRuntimeApiCall {
+    trait_name: &'static str,
+    method_name: &'static str,
+    args: Vec<u8>,
+}
+
+impl subxt::runtime_api::Payload for RawRuntimeApiCall {
+    type ReturnType = DecodedValueThunk;
+
+    fn trait_name(&self) -> &str {
+        self.trait_name
+    }
+
+    fn method_name(&self) -> &str {
+        self.method_name
+    }
+
+    fn encode_args_to(
+        &self,
+        _metadata: &subxt::Metadata,
+        out: &mut Vec<u8>,
+    ) -> Result<(), subxt::Error> {
+        out.extend_from_slice(&self.args);
+        Ok(())
+    }
+}
+
+/// Call a runtime API with pre-encoded arguments at the latest block.
+async fn raw_runtime_api_call(
+    api: &OnlineClient<ChainConfig>,
+    trait_name: &'static str,
+    method_name: &'static str,
+    args: Vec<u8>,
+) -> PyResult<Value<u32>> {
+    let runtime_api_call = RawRuntimeApiCall {
+        trait_name,
+        method_name,
+        args,
+    };
+    api.runtime_api()
+        .at_latest()
+        .await
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?
+        .call(runtime_api_call)
+        .await
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?
+        .to_value()
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
+}
+
+/// The result of a dry run.
+enum DryRunOutcome {
+    Success,
+    /// The call would fail, with the decoded `DispatchError`.
+    DispatchError(Value<u32>),
+    /// The transaction is invalid, with the decoded `TransactionValidityError`.
+    ValidityError(Value<u32>),
+}
+
+/// Dispatch a call from a signed origin with `DryRunApi.dry_run_call`.
+///
+/// Returns None if the runtime doesn't have the API.
+async fn dry_run_call(
+    api: &OnlineClient<ChainConfig>,
+    call_data: Vec<u8>,
+    account_id: &ChainAccountId,
+) -> PyResult<Option<DryRunOutcome>> {
+    let metadata = api.metadata();
+    let input_tys: Vec<u32> = metadata
+        .runtime_api_trait_by_name("DryRunApi")
+        .and_then(|runtime_api| {
+            runtime_api
+                .method_by_name("dry_run_call")
+                .map(|method| method.inputs().map(|input| input.ty).collect())
+        })
+        .unwrap_or_default();
+    let origin_ty = match input_tys.first() {
+        Some(&origin_ty) => origin_ty,
+        None => return Ok(None),
+    };
+
+    let encode_error = |e: subxt::ext::scale_encode::Error| {
+        PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string())
+    };
+    let mut args = Vec::new();
+    // The origin is an `OriginCaller`, and the call a pre-encoded `RuntimeCall`
+    let origin = Value::unnamed_variant(
+        "system",
+        vec![Value::unnamed_variant(
+            "Signed",
+            vec![Value::from_bytes(account_id.encode())],
+        )],
+    );
+    origin
+        .encode_as_type_to(origin_ty, metadata.types(), &mut args)
+        .map_err(encode_error)?;
+    args.extend(call_data);
+    // Newer versions of the API also take the XCM version to report forwarded messages in
+    if let Some(&version_ty) = input_tys.get(2) {
+        Value::u128(DRY_RUN_XCM_VERSION.into())
+            .encode_as_type_to(version_ty, metadata.types(), &mut args)
+            .map_err(encode_error)?;
+    }
+
+    // Result<CallDryRunEffects, XcmDryRunApiError>
+    let result = raw_runtime_api_call(api, "DryRunApi", "dry_run_call", args).await?;
+    let execution_result = match variant_parts(&result) {
+        Some(("Ok", Some(effects))) => effects.at("execution_result"),
+        _ => None,
+    }
+    .ok_or_else(|| {
+        PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
+            "DryRunApi.dry_run_call failed: {}",
+            result
+        ))
+    })?;
+    // DispatchResultWithPostInfo = Result<PostDispatchInfo, DispatchErrorWithPostInfo>
+    Ok(Some(match variant_parts(execution_result) {
+        Some(("Ok", _)) => DryRunOutcome::Success,
+        Some(("Err", Some(error))) => {
+            DryRunOutcome::DispatchError(error.at("error").unwrap_or(error).clone())
+        }
+        _ => DryRunOutcome::DispatchError(execution_result.clone()),
+    }))
+}
+
+/// Check a signed transaction with `TaggedTransactionQueue.validate_transaction`, as the
+/// transaction pool would on top of the block `at`.
+///
+/// Returns the decoded `TransactionValidityError` if the transaction is invalid.
+async fn validate_transaction(
+    api: &OnlineClient<ChainConfig>,
+    extrinsic: &[u8],
+    at: H256,
+) -> PyResult<Option<Value<u32>>> {
+    let metadata = api.metadata();
+    let input_tys: Vec<u32> = metadata
+        .runtime_api_trait_by_name("TaggedTransactionQueue")
+        .and_then(|runtime_api| {
+            runtime_api
+                .method_by_name("validate_transaction")
+                .map(|method| method.inputs().map(|input| input.ty).collect())
+        })
+        .ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+                "The runtime has no TaggedTransactionQueue.validate_transaction API",
+            )
+        })?;
+
+    let mut args = Vec::new();
+    // The source is a `TransactionSource`; transactions submitted over RPC are external
+    if let Some(&source_ty) = input_tys.first() {
+        Value::unnamed_variant("External", vec![])
+            .encode_as_type_to(source_ty, metadata.types(), &mut args)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+    }
+    args.extend_from_slice(extrinsic);
+    // Newer versions of the API also take the hash of the block to validate on top of
+    if input_tys.len() > 2 {
+        at.encode_to(&mut args);
+    }
+
+    let runtime_api_call = RawRuntimeApiCall {
+        trait_name: "TaggedTransactionQueue",
+        method_name: "validate_transaction",
+        args,
+    };
+    let result = api
+        .runtime_api()
+        .at(at)
+        .call(runtime_api_call)
+        .await
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?
+        .to_value()
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+    // TransactionValidity = Result<ValidTransaction, TransactionValidityError>
+    Ok(match variant_parts(&result) {
+        Some(("Ok", _)) => None,
+        Some(("Err", Some(error))) => Some(error.clone()),
+        _ => Some(result.clone()),
+    })
+}
+
+/// Apply a signed transaction on top of a block with the `system_dryRun` RPC method.
+///
+/// Unlike calling `BlockBuilder.apply_extrinsic` directly, the node initializes a new block
+/// first, so mortal transactions checkpointed at `at` are valid.
+async fn system_dry_run(
+    api: &OnlineClient<ChainConfig>,
+    rpc: &LegacyRpcMethods<ChainConfig>,
+    extrinsic: &[u8],
+    at: H256,
+) -> PyResult<DryRunOutcome> {
+    let metadata = api.metadata();
+    let output_ty = metadata
+        .runtime_api_trait_by_name("BlockBuilder")
+        .and_then(|runtime_api| {
+            runtime_api
+                .method_by_name("apply_extrinsic")
+                .map(|method| method.output_ty())
+        })
+        .ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+                "The runtime has no BlockBuilder.apply_extrinsic API",
+            )
+        })?;
+    let result_bytes = rpc
+        .dry_run(extrinsic, Some(at))
+        .await
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+    let result = decode_as_type(&mut &result_bytes.0[..], output_ty, metadata.types())
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+    // ApplyExtrinsicResult = Result<Result<(), DispatchError>, TransactionValidityError>
+    Ok(match variant_parts(&result) {
+        Some(("Ok", Some(dispatch_result))) => match variant_parts(dispatch_result) {
+            Some(("Ok", _)) => DryRunOutcome::Success,
+            Some(("Err", Some(error))) => DryRunOutcome::DispatchError(error.clone()),
+            _ => DryRunOutcome::DispatchError(dispatch_result.clone()),
+        },
+        Some(("Err", Some(error))) => DryRunOutcome::ValidityError(error.clone()),
+        _ => DryRunOutcome::ValidityError(result.clone()),
+    })
+}
+
+/// Split a decoded enum value into its variant name and first field.
+fn variant_parts<T>(value: &Value<T>) -> Option<(&str, Option<&Value<T>>)> {
+    match &value.value {
+        ValueDef::Variant(variant) => Some((variant.name.as_str(), variant.values.values().next())),
+        _ => None,
+    }
+}
+
+/// Convert a decoded `DispatchError` to a Python object.
+///
+/// Module errors are resolved to their pallet and error names via the runtime metadata; other
+/// errors are returned as decoded values.
+fn dispatch_error_value_to_py_object(
+    py: Python,
+    metadata: &subxt::Metadata,
+    error: &Value<u32>,
+) -> PyResult<PyObject> {
+    let module_error = match variant_parts(error) {
+        Some(("Module", Some(module_error))) => module_error,
+        _ => return decoded_value_to_py_object(py, error),
+    };
+    let pallet_index = module_error.at("index").and_then(|v| v.as_u128());
+    let error_index = module_error.at("error").at(0).and_then(|v| v.as_u128());
+    let pallet = pallet_index.and_then(|index| metadata.pallet_by_index(index as u8));
+    let variant = pallet.as_ref().and_then(|pallet| {
+        error_index.and_then(|index| pallet.error_variant_by_index(index as u8))
+    });
+    match (&pallet, variant) {
+        (Some(pallet), Some(variant)) => {
+            let dict = PyDict::new(py);
+            dict.set_item("pallet", pallet.name())?;
+            dict.set_item("error", &variant.name)?;
+            dict.set_item("docs", variant.docs.join("\n"))?;
+            Ok(dict.to_object(py))
+        }
+        _ => decoded_value_to_py_object(py, error),
+    }
+}
+
+/// Verify a signature over an arbitrary message.
+///
+/// Args:
+///     signature (bytes): The signature to check.
+///     message (bytes): The message that was signed.
+///     public_key (bytes): The signer's public key. For Ethereum signatures this may also be the
+///         20-byte account id.
+///     scheme (str, optional): "sr25519" (default), "ed25519", "ecdsa" or "ethereum".
+///     wrap (bool, optional): Wrap the message in `<Bytes>...</Bytes>` before verifying, as
+///         polkadot-js `signRaw` does. Defaults to False.
+///
+/// Returns:
+///     bool: True if the signature is valid.
+///
+/// Raises:
+///     ValueError: If the signature or public key has the wrong length for the scheme.
+#[pyfunction]
+#[pyo3(signature = (signature, message, public_key, scheme="sr25519", wrap=false))]
+fn verify(
+    signature: &[u8],
+    message: &[u8],
+    public_key: &[u8],
+    scheme: &str,
+    wrap: bool,
+) -> PyResult<bool> {
+    let scheme = KeyScheme::parse(scheme)?;
+    let message = if wrap {
+        wrap_bytes(message)
+    } else {
+        message.to_vec()
+    };
+    let invalid_length = |what: &str| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid {} length", what))
+    };
+
+    match scheme {
+        KeyScheme::Sr25519 => {
+            let signature = signature
+                .try_into()
+                .map_err(|_| invalid_length("signature"))?;
+            let public_key = public_key
+                .try_into()
+                .map_err(|_| invalid_length("public key"))?;
+            Ok(subxt_signer::sr25519::verify(
+                &subxt_signer::sr25519::Signature(signature),
+                &message,
+                &subxt_signer::sr25519::PublicKey(public_key),
+            ))
+        }
+        KeyScheme::Ed25519 => {
+            let signature: [u8; 64] = signature
+                .try_into()
+                .map_err(|_| invalid_length("signature"))?;
+            let public_key: [u8; 32] = public_key
+                .try_into()
+                .map_err(|_| invalid_length("public key"))?;
+            let verifying_key = match ed25519_dalek::VerifyingKey::from_bytes(&public_key) {
+                Ok(verifying_key) => verifying_key,
+                Err(_) => return Ok(false),
+            };
+            Ok(ed25519_dalek::Verifier::verify(
+                &verifying_key,
+                &message,
+                &ed25519_dalek::Signature::from_bytes(&signature),
+            )
+            .is_ok())
+        }
+        KeyScheme::Ecdsa => {
+            let signature = signature
+                .try_into()
+                .map_err(|_| invalid_length("signature"))?;
+            let public_key = public_key
+                .try_into()
+                .map_err(|_| invalid_length("public key"))?;
+            Ok(subxt_signer::ecdsa::verify(
+                &subxt_signer::ecdsa::Signature(signature),
+                &message,
+                &subxt_signer::ecdsa::PublicKey(public_key),
+            ))
+        }
+        KeyScheme::Ethereum => {
+            if signature.len() != 65 {
+                return Err(invalid_length("signature"));
+            }
+            if public_key.len() != 33 && public_key.len() != 20 {
+                return Err(invalid_length("public key"));
+            }
+            let recovered = match secp256k1_recover(signature, keccak_256(&message)) {
+                Some(recovered) => recovered,
+                None => return Ok(false),
+            };
+            if public_key.len() == 20 {
+                let uncompressed = recovered.serialize_uncompressed();
+                Ok(keccak_256(&uncompressed[1..])[12..] == *public_key)
+            } else {
+                Ok(recovered.serialize()[..] == *public_key)
+            }
+        }
+    }
+}
+
+/// Wrap a message in `<Bytes>...</Bytes>`, unless it is already wrapped.
+///
+/// This matches polkadot-js `u8aWrapBytes`, which `signRaw` applies before signing.
+fn wrap_bytes(message: &[u8]) -> Vec<u8> {
+    const PREFIX: &[u8] = b"<Bytes>";
+    const POSTFIX: &[u8] = b"</Bytes>";
+    if message.starts_with(PREFIX) && message.ends_with(POSTFIX) {
+        return message.to_vec();
+    }
+    [PREFIX, message, POSTFIX].concat()
+}
+
+/// Return the public key of a keypair.
+fn keypair_public_key(keypair: &KeypairKind) -> Vec<u8> {
+    match keypair {
+        KeypairKind::Sr25519(keypair) => keypair.public_key().to_vec(),
+        KeypairKind::Ed25519(keypair) => keypair.public_key().to_vec(),
+        KeypairKind::Ecdsa(keypair) => keypair.public_key().to_vec(),
+        KeypairKind::Ethereum(keypair) => keypair.public_key().to_vec(),
+    }
+}
+
+/// The PKCS8 header polkadot-js prepends to secret keys in JSON keystores.
+const PKCS8_HEADER: [u8; 16] = [48, 83, 2, 1, 1, 48, 5, 6, 3, 43, 101, 112, 4, 34, 4, 32];
+/// The PKCS8 divider polkadot-js places between the secret and public keys.
+const PKCS8_DIVIDER: [u8; 5] = [161, 35, 3, 33, 0];
+/// The default scrypt parameters used by polkadot-js: N = 2^15, p = 1, r = 8.
+const SCRYPT_LOG_N: u8 = 15;
+const SCRYPT_P: u32 = 1;
+const SCRYPT_R: u32 = 8;
+/// The length of the scrypt header (salt, N, p, r) in front of an encrypted keystore.
+const SCRYPT_HEADER_LEN: usize = 44;
+/// The length of an xsalsa20-poly1305 nonce.
+const NONCE_LEN: usize = 24;
+
+/// Return the secret key of a keypair in the format polkadot-js stores it in a keystore.
+fn keystore_secret(keypair: &KeypairKind) -> Vec<u8> {
+    match keypair {
+        KeypairKind::Sr25519(keypair) => keypair.secret_key().to_vec(),
+        KeypairKind::Ed25519(keypair) => keypair.secret_key().to_vec(),
+        KeypairKind::Ecdsa(keypair) => keypair.secret_key().to_vec(),
+        KeypairKind::Ethereum(keypair) => keypair.secret_key().to_vec(),
+    }
+}
+
+/// Derive the xsalsa20-poly1305 key from a password with scrypt, as polkadot-js `scryptEncode` does.
+fn scrypt_key(password: &str, salt: &[u8], log_n: u8, p: u32, r: u32) -> PyResult<[u8; 32]> {
+    let params = scrypt::Params::new(log_n, r, p, 64).map_err(|e| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid scrypt params: {}", e))
+    })?;
+    let mut output = [0u8; 64];
+    scrypt::scrypt(password.as_bytes(), salt, &params, &mut output)
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+    let mut key = [0u8; 32];
+    key.copy_from_slice(&output[..32]);
+    Ok(key)
+}
+
+/// Encrypt a PKCS8-encoded keypair in the polkadot-js v3 keystore format.
+fn keystore_encrypt(plaintext: &[u8], password: &str) -> PyResult<Vec<u8>> {
+    let mut salt = [0u8; 32];
+    let mut nonce = [0u8; NONCE_LEN];
+    getrandom::getrandom(&mut salt)
+        .and_then(|_| getrandom::getrandom(&mut nonce))
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+    let key = scrypt_key(password, &salt, SCRYPT_LOG_N, SCRYPT_P, SCRYPT_R)?;
+    let ciphertext = XSalsa20Poly1305::new(Key::from_slice(&key))
+        .encrypt(Nonce::from_slice(&nonce), plaintext)
+        .map_err(|_| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>("Encryption failed"))?;
+
+    let mut encoded = Vec::with_capacity(SCRYPT_HEADER_LEN + NONCE_LEN + ciphertext.len());
+    encoded.extend_from_slice(&salt);
+    encoded.extend_from_slice(&(1u32 << SCRYPT_LOG_N).to_le_bytes());
+    encoded.extend_from_slice(&SCRYPT_P.to_le_bytes());
+    encoded.extend_from_slice(&SCRYPT_R.to_le_bytes());
+    encoded.extend_from_slice(&nonce);
+    encoded.extend_from_slice(&ciphertext);
+    Ok(encoded)
+}
+
+/// Decrypt the `encoded` field of a polkadot-js keystore, returning the PKCS8-encoded keypair.
+///
+/// Both scrypt-based keystores (v3) and the older format, where the password is used as the key
+/// directly, are supported.
+fn keystore_decrypt(encoded: &[u8], password: &str, is_scrypt: bool) -> PyResult<Vec<u8>> {
+    let invalid = || PyErr::new::<pyo3::exceptions::PyValueError, _>("Invalid encoded keystore");
+
+    let (key, encrypted) = if is_scrypt {
+        if encoded.len() < SCRYPT_HEADER_LEN {
+            return Err(invalid());
+        }
+        let read_u32 = |offset: usize| {
+            u32::from_le_bytes(
+                encoded[offset..offset + 4]
+                    .try_into()
+                    .expect("4 bytes; qed"),
+            )
+        };
+        // The parameters come from the file, so only accept the ones polkadot-js writes rather
+        // than letting a keystore pick an arbitrarily expensive key derivation
+        let (n, p, r) = (read_u32(32), read_u32(36), read_u32(40));
+        if (n, p, r) != (1 << SCRYPT_LOG_N, SCRYPT_P, SCRYPT_R) {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Unsupported scrypt parameters: N={}, p={}, r={}",
+                n, p, r
+            )));
+        }
+        let key = scrypt_key(password, &encoded[..32], SCRYPT_LOG_N, SCRYPT_P, SCRYPT_R)?;
+        (key, &encoded[SCRYPT_HEADER_LEN..])
+    } else {
+        let mut key = [0u8; 32];
+        let password = password.as_bytes();
+        let len = password.len().min(32);
+        key[..len].copy_from_slice(&password[..len]);
+        (key, encoded)
+    };
+
+    if encrypted.len() < NONCE_LEN {
+        return Err(invalid());
+    }
+    let (nonce, ciphertext) = encrypted.split_at(NONCE_LEN);
+    XSalsa20Poly1305::new(Key::from_slice(&key))
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "Unable to decode using the supplied password",
+            )
+        })
+}
+
+/// Split a PKCS8-encoded keypair into its secret and public keys.
+fn pkcs8_decode(plaintext: &[u8], secret_len: usize) -> PyResult<(&[u8], &[u8])> {
+    let divider_offset = PKCS8_HEADER.len() + secret_len;
+    let public_offset = divider_offset + PKCS8_DIVIDER.len();
+    if plaintext.len() < public_offset
+        || plaintext[..PKCS8_HEADER.len()] != PKCS8_HEADER
+        || plaintext[divider_offset..public_offset] != PKCS8_DIVIDER
+    {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "Invalid PKCS8 keypair in keystore",
+        ));
+    }
+    Ok((
+        &plaintext[PKCS8_HEADER.len()..divider_offset],
+        &plaintext[public_offset..],
+    ))
+}
+
+/// Return the on-chain account id for a keypair.
+///
+/// ECDSA public keys are 33 bytes long, so like Substrate we hash them with blake2-256. Ethereum
+/// keypairs map to 20-byte account ids.
+fn chain_account_id(keypair: &KeypairKind) -> ChainAccountId {
+    match keypair {
+        KeypairKind::Sr25519(keypair) => ChainAccountId::Id32(AccountId32(keypair.public_key())),
+        KeypairKind::Ed25519(keypair) => ChainAccountId::Id32(AccountId32(keypair.public_key())),
+        KeypairKind::Ecdsa(keypair) => {
+            ChainAccountId::Id32(AccountId32(blake2_256(&keypair.public_key())))
+        }
+        KeypairKind::Ethereum(keypair) => ChainAccountId::Id20(keypair.account_id()),
+    }
+}
+
+/// Encode an account id or public key as an SS58 address.
+///
+/// Args:
+///     account_id (bytes): The account id or public key to encode; 1, 2, 4, 8, 32 or 33 bytes.
+///     prefix (int, optional): The SS58 network prefix, 42 (generic Substrate) by default.
+///
+/// Returns:
+///     str: The SS58-encoded address.
+///
+/// Raises:
+///     ValueError: If the prefix is out of range or the account id has an unsupported length.
+#[pyfunction]
+#[pyo3(signature = (account_id, prefix=42))]
+fn ss58_encode(account_id: &[u8], prefix: u16) -> PyResult<String> {
+    let checksum_len = ss58_checksum_len(account_id.len()).ok_or_else(|| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Invalid SS58 account id length: {}",
+            account_id.len()
+        ))
+    })?;
+    let mut data = match prefix {
+        0..=63 => vec![prefix as u8],
+        64..=16383 => {
+            // Two byte prefixes are split into 14 bits, as in `sp_core::crypto::Ss58Codec`
+            let first = ((prefix & 0b0000_0000_1111_1100) as u8) >> 2;
+            let second = ((prefix >> 8) as u8) | (((prefix & 0b0000_0000_0000_0011) as u8) << 6);
+            vec![first | 0b0100_0000, second]
+        }
+        _ => {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Invalid SS58 prefix: {}",
+                prefix
+            )))
+        }
+    };
+    data.extend_from_slice(account_id);
+    let checksum = ss58_checksum(&data);
+    data.extend_from_slice(&checksum[..checksum_len]);
+    Ok(data.to_base58())
+}
+
+/// Decode an SS58 address, validating its checksum.
+///
+/// Args:
+///     address (str): The SS58-encoded address.
+///
+/// Returns:
+///     tuple: The decoded account id bytes and the network prefix.
+///
+/// Raises:
+///     ValueError: If the address is not valid base58, has an invalid prefix or a bad checksum.
+#[pyfunction]
+#[pyo3(name = "ss58_decode")]
+fn py_ss58_decode(py: Python, address: &str) -> PyResult<(PyObject, u16)> {
+    let (account_id, prefix) = ss58_decode(address)?;
+    Ok((PyBytes::new(py, &account_id).into(), prefix))
+}
+
+/// Decode an SS58 address into its account id bytes and network prefix.
+fn ss58_decode(address: &str) -> PyResult<(Vec<u8>, u16)> {
+    let data = address.from_base58().map_err(|e| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid base58 string: {:?}", e))
+    })?;
+    if data.len() < 3 {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "Invalid SS58 address length",
+        ));
+    }
+
+    let (prefix_len, prefix) = match data[0] {
+        0..=63 => (1, data[0] as u16),
+        64..=127 => {
+            // Reverse of the 14 bit prefix split done in `ss58_encode`
+            let lower = (data[0] << 2) | (data[1] >> 6);
+            let upper = data[1] & 0b0011_1111;
+            (2, (lower as u16) | ((upper as u16) << 8))
+        }
+        _ => {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "Invalid SS58 prefix",
+            ))
+        }
+    };
+    // The checksum length depends on the account id length, which is only known once we know
+    // the checksum length; the allowed lengths never overlap
+    let body_len = data.len().saturating_sub(prefix_len);
+    let checksum_len = [2, 1]
+        .iter()
+        .copied()
+        .find(|&len| body_len > len && ss58_checksum_len(body_len - len) == Some(len))
+        .ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>("Invalid SS58 address length")
+        })?;
+
+    let checksum_start = data.len() - checksum_len;
+    let checksum = ss58_checksum(&data[..checksum_start]);
+    if data[checksum_start..] != checksum[..checksum_len] {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "Invalid SS58 checksum",
+        ));
+    }
+    Ok((data[prefix_len..checksum_start].to_vec(), prefix))
+}
+
+/// The SS58 checksum length for an account id of the given length, as in `sp_core` and
+/// polkadot-js: one byte for 1, 2, 4 and 8 byte ids, two bytes for 32 and 33 byte public keys.
+fn ss58_checksum_len(account_id_len: usize) -> Option<usize> {
+    match account_id_len {
+        1 | 2 | 4 | 8 => Some(1),
+        32 | 33 => Some(2),
+        _ => None,
+    }
+}
+
+/// Compute the SS58 checksum hash (blake2b-512 of "SS58PRE" followed by the data).
+fn ss58_checksum(data: &[u8]) -> [u8; 64] {
+    let mut hasher = Blake2b512::new();
+    hasher.update(b"SS58PRE");
+    hasher.update(data);
+    let mut hash = [0u8; 64];
+    hash.copy_from_slice(&hasher.finalize());
+    hash
+}
+
+/// Hash some bytes with blake2b-256.
+fn blake2_256(data: &[u8]) -> [u8; 32] {
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&Blake2b::<U32>::digest(data));
+    hash
+}
+
+/// Hash some bytes with keccak-256.
+fn keccak_256(data: &[u8]) -> [u8; 32] {
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&Keccak256::digest(data));
+    hash
+}
+
+/// Compute HMAC-SHA512, as used by BIP-32 key derivation.
+fn hmac_sha512(key: &[u8], data: &[u8]) -> [u8; 64] {
+    let mut mac =
+        Hmac::<Sha512>::new_from_slice(key).expect("HMAC can take a key of any size; qed");
+    mac.update(data);
+    let mut output = [0u8; 64];
+    output.copy_from_slice(&mac.finalize().into_bytes());
+    output
+}
+
+/// A subscription to new blocks on the blockchain.
+///
+/// This class provides an asynchronous iterator over new blocks as they are finalized.
+///
+/// .. code-block:: python
+///
+///     async for block in subscription:
+///         print(block)
+#[pyclass]
+struct BlockSubscription {
+    blocks_stream:
+        Arc<tokio::sync::Mutex<StreamOfResults<Block<ChainConfig, OnlineClient<ChainConfig>>>>>,
+}
+
+#[pymethods]
+impl BlockSubscription {
+    /// Return the asynchronous iterator object.
+    fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Return the next block from the subscription.
+    ///
+    /// Yields:
+    ///     dict: A dictionary containing the block number, block hash, and a list of extrinsics.
+    ///
+    /// Raises:
+    ///     StopAsyncIteration: When no more blocks are available.
+    fn __anext__<'a>(&self, py: Python<'a>) -> PyResult<Option<PyObject>> {
+        let blocks_stream = self.blocks_stream.clone();
+        let future = future_into_py(py, async move {
+            let mut blocks_stream = blocks_stream.lock().await;
+            match blocks_stream.next().await {
+                Some(block_result) => {
+                    let block = block_result.map_err(|e| {
+                        PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string())
+                    })?;
+
+                    // Get block number and hash
+                    let block_number = block.header().number;
+                    let block_hash = block.hash();
+
+                    // Get the extrinsics
+                    let extrinsics = block.extrinsics().await.map_err(|e| {
+                        PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string())
+                    })?;
+
+                    let mut extrinsics_info = vec![];
+
+                    for ext_result in extrinsics.iter() {
+                        let ext = ext_result.map_err(|e| {
+                            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string())
+                        })?;
+
+                        // Get signed extensions
+                        let signed_extensions = match ext.signed_extensions() {
+                            Some(signed_extensions) => signed_extensions,
+                            None => continue, // Skip unsigned extrinsics
+                        };
+
+                        // Get metadata
+                        let meta = ext.extrinsic_metadata().map_err(|e| {
+                            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string())
+                        })?;
+                        let pallet_name = meta.pallet.name();
+                        let call_name = &meta.variant.name;
+
+                        // Get field values
+                        let fields = ext.field_values().map_err(|e| {
+                            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string())
+                        })?;
+
+                        // Convert fields to Python object
+                        let py_fields = Python::with_gil(|py| composite_to_py_object(py, &fields))?;
+
+                        // Collect signed extensions
+                        let py_signed_extensions = Python::with_gil(|py| -> PyResult<PyObject> {
+                            let mut ext_list = Vec::new();
+                            for se_result in signed_extensions.iter() {
+                                let se = match se_result {
+                                    Ok(se) => se,
+                                    Err(_) => continue,
+                                };
+                                let name = se.name();
+                                if ["CheckMortality", "CheckNonce", "ChargeTransactionPayment"]
+                                    .contains(&name)
+                                {
+                                    let value = match se.value() {
+                                        Ok(value) => value,
+                                        Err(_) => continue,
+                                    };
+                                    let value_py = decoded_value_to_py_object(py, &value)?;
+                                    let dict = PyDict::new(py);
+                                    dict.set_item("name", name)?;
+                                    dict.set_item("value", value_py)?;
+                                    ext_list.push(dict.to_object(py));
+                                }
+                            }
+                            Ok(PyList::new(py, ext_list).to_object(py))
+                        })?;
+
+                        // Collect extrinsic information
+                        let extrinsic_info = Python::with_gil(|py| -> PyResult<PyObject> {
+                            let dict = PyDict::new(py);
+                            dict.set_item("pallet", pallet_name)?;
+                            dict.set_item("call", call_name)?;
+                            dict.set_item("fields", py_fields)?;
+                            dict.set_item("signed_extensions", py_signed_extensions)?;
+                            Ok(dict.to_object(py))
+                        })?;
+
+                        extrinsics_info.push(extrinsic_info);
+                    }
+
+                    // Create a Python dictionary with block info
+                    let py_block_info = Python::with_gil(|py| -> PyResult<PyObject> {
+                        let dict = PyDict::new(py);
+                        dict.set_item("block_number", block_number)?;
+                        dict.set_item("block_hash", format!("{:?}", block_hash))?;
+                        dict.set_item("extrinsics", PyList::new(py, extrinsics_info))?;
+                        Ok(dict.to_object(py))
+                    })?;
+                    Ok(Some(py_block_info))
+                }
+                None => Err(PyErr::new::<pyo3::exceptions::PyStopAsyncIteration, _>(
+                    "No more blocks",
+                )),
+            }
+        });
+        Ok(Some(future?.into()))
+    }
+}
+
+/// An asynchronous iterator over storage key-value pairs.
+///
+/// This class allows you to iterate over storage entries retrieved from the blockchain. Keys are
+/// fetched a page at a time with `state_getKeysPaged`, and `last_key` can be saved to resume the
+/// iteration later by passing it as `start_key` to `SubxtClient.storage_iter`.
+#[pyclass]
+struct StorageIterator {
+    pages: Arc<tokio::sync::Mutex<StoragePages>>,
+    last_key: Arc<std::sync::Mutex<Option<Vec<u8>>>>,
+}
+
+/// The paging state of a `StorageIterator`.
+struct StoragePages {
+    rpc: LegacyRpcMethods<ChainConfig>,
+    metadata: subxt::Metadata,
+    block_hash: H256,
+    prefix: Vec<u8>,
+    page_size: u32,
+    /// The hasher and type id of each key of the storage map.
+    keys: Vec<(StorageHasher, u32)>,
+    value_ty: u32,
+    /// The last key fetched, which the next page starts after.
+    cursor: Option<Vec<u8>>,
+    /// Fetched key-value pairs that haven't been yielded yet.
+    buffer: VecDeque<(Vec<u8>, Vec<u8>)>,
+    done: bool,
+}
+
+impl StoragePages {
+    /// Return the next key-value pair, fetching the next page if needed.
+    async fn next(&mut self) -> PyResult<Option<(Vec<u8>, Vec<u8>)>> {
+        while self.buffer.is_empty() && !self.done {
+            let keys = self
+                .rpc
+                .state_get_keys_paged(
+                    &self.prefix,
+                    self.page_size,
+                    self.cursor.as_deref(),
+                    Some(self.block_hash),
+                )
+                .await
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+            self.done = keys.len() < self.page_size as usize;
+            self.cursor = keys.last().cloned().or_else(|| self.cursor.take());
+
+            let mut values = query_storage_at(&self.rpc, &keys, self.block_hash).await?;
+            for key in keys {
+                // Skip entries removed since the keys were listed; can't happen at a fixed block
+                if let Some(Some(value)) = values.remove(&key) {
+                    self.buffer.push_back((key, value));
+                }
+            }
+        }
+        Ok(self.buffer.pop_front())
+    }
+
+    /// Decode the keys of a storage map from a full storage key.
+    ///
+    /// Keys hashed with a non-concat hasher can't be recovered, and are returned as None.
+    fn decode_keys(&self, py: Python, key_bytes: &[u8]) -> PyResult<Vec<PyObject>> {
+        let invalid = |e: String| {
+            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Invalid storage key: {}", e))
+        };
+        // Skip the twox128 hashes of the pallet and entry names
+        let mut bytes = key_bytes
+            .get(32..)
+            .ok_or_else(|| invalid("too short".to_string()))?;
+        let mut keys = Vec::with_capacity(self.keys.len());
+        for (hasher, ty_id) in &self.keys {
+            let (hash_len, concat) = match hasher {
+                StorageHasher::Blake2_128Concat => (16, true),
+                StorageHasher::Twox64Concat => (8, true),
+                StorageHasher::Identity => (0, true),
+                StorageHasher::Blake2_128 | StorageHasher::Twox128 => (16, false),
+                StorageHasher::Blake2_256 | StorageHasher::Twox256 => (32, false),
+            };
+            let hash = bytes
+                .get(..hash_len)
+                .ok_or_else(|| invalid("too short".to_string()))?;
+            bytes = &bytes[hash_len..];
+            if concat {
+                let key = decode_as_type(&mut bytes, *ty_id, self.metadata.types())
+                    .map_err(|e| invalid(e.to_string()))?;
+                keys.push(decoded_value_to_py_object(py, &key)?);
+            } else {
+                // The key can't be recovered from its hash, so return the hash itself
+                keys.push(PyBytes::new(py, hash).to_object(py));
+            }
+        }
+        Ok(keys)
+    }
+}
+
+#[pymethods]
+impl StorageIterator {
+    /// The key bytes of the last item yielded, to resume the iteration from.
+    ///
+    /// Returns:
+    ///     bytes | None: The full storage key, or None if nothing has been yielded yet.
+    #[getter]
+    fn last_key<'py>(&self, py: Python<'py>) -> Option<&'py PyBytes> {
+        self.last_key
+            .lock()
+            .expect("lock is never poisoned; qed")
+            .as_ref()
+            .map(|key| PyBytes::new(py, key))
+    }
+
+    /// Return the asynchronous iterator object.
+    fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Return the next storage key-value pair from the iterator.
+    ///
+    /// Yields:
+    ///     dict: A dictionary containing the key bytes, keys, and value. Keys hashed without
+    ///         the key appended, e.g. with `Blake2_128`, can't be recovered, so their hash bytes
+    ///         are given instead.
+    ///
+    /// Raises:
+    ///     StopAsyncIteration: When no more items are available.
+    fn __anext__<'a>(&self, py: Python<'a>) -> PyResult<Option<PyObject>> {
+        let pages = self.pages.clone();
+        let last_key = self.last_key.clone();
+        let future = future_into_py(py, async move {
+            let mut pages = pages.lock().await;
+            if let Some((key_bytes, value_bytes)) = pages.next().await? {
+                let value = decode_as_type(
+                    &mut &value_bytes[..],
+                    pages.value_ty,
+                    pages.metadata.types(),
+                )
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+                let py_dict = Python::with_gil(|py| -> PyResult<PyObject> {
+                    let dict = PyDict::new(py);
+                    dict.set_item("key_bytes", PyBytes::new(py, &key_bytes))?;
+                    dict.set_item("keys", pages.decode_keys(py, &key_bytes)?)?;
+                    dict.set_item("value", decoded_value_to_py_object(py, &value)?)?;
+                    Ok(dict.to_object(py))
+                })?;
+                *last_key.lock().expect("lock is never poisoned; qed") = Some(key_bytes);
+                Ok(Some(py_dict))
+            } else {
+                Err(PyErr::new::<pyo3::exceptions::PyStopAsyncIteration, _>(
+                    "Iterator exhausted",
+                ))
+            }
+        });
+        Ok(Some(future?.into()))
+    }
+}
+
+/// The progress of a submitted transaction.
+///
+/// This class is an asynchronous iterator over the transaction's status updates, and can also
+/// wait for the transaction to be finalized.
+///
+/// .. code-block:: python
+///
+///     async for status in progress:
+///         print(status["status"])
+#[pyclass]
+struct TransactionProgress {
+    tx_hash: String,
+    progress: Arc<tokio::sync::Mutex<Option<TxProgress<ChainConfig, OnlineClient<ChainConfig>>>>>,
+    nonces: Arc<NonceManager>,
+    /// The signer, whose nonce is resynced if the transaction fails. None if it's unsigned.
+    account_id: Option<ChainAccountId>,
+}
+
+impl TransactionProgress {
+    fn new(
+        progress: TxProgress<ChainConfig, OnlineClient<ChainConfig>>,
+        nonces: Arc<NonceManager>,
+        account_id: Option<ChainAccountId>,
+    ) -> Self {
+        TransactionProgress {
+            tx_hash: format!("{:?}", progress.extrinsic_hash()),
+            progress: Arc::new(tokio::sync::Mutex::new(Some(progress))),
+            nonces,
+            account_id,
+        }
+    }
+}
+
+#[pymethods]
+impl TransactionProgress {
+    /// The transaction hash as a hexadecimal string.
+    #[getter]
+    fn tx_hash(&self) -> String {
+        self.tx_hash.clone()
+    }
+
+    /// Return the asynchronous iterator object.
+    fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Return the next status update of the transaction.
+    ///
+    /// Yields:
+    ///     dict: A dictionary with the `status`, one of "Validated", "Broadcast",
+    ///         "NoLongerInBestBlock", "InBestBlock", "InFinalizedBlock", "Error", "Invalid" or
+    ///         "Dropped". In-block statuses also contain the `block_hash` and `extrinsic_hash`,
+    ///         "Broadcast" the `num_peers` and the error statuses a `message`.
+    ///
+    /// Raises:
+    ///     StopAsyncIteration: After the final status update.
+    ///     RuntimeError: If the status subscription fails.
+    fn __anext__<'a>(&self, py: Python<'a>) -> PyResult<Option<PyObject>> {
+        let progress = self.progress.clone();
+        let nonces = self.nonces.clone();
+        let account_id = self.account_id.clone();
+        let future = future_into_py(py, async move {
+            let mut progress = progress.lock().await;
+            let status = match progress.as_mut() {
+                Some(progress) => progress.next().await,
+                None => None,
+            };
+            match status {
+                Some(status) => {
+                    let status = status.map_err(|e| {
+                        PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string())
+                    })?;
+                    if let TxStatus::Error { .. }
+                    | TxStatus::Invalid { .. }
+                    | TxStatus::Dropped { .. } = status
+                    {
+                        if let Some(account_id) = &account_id {
+                            nonces.resync(account_id).await;
+                        }
+                    }
+                    Python::with_gil(|py| tx_status_to_py_object(py, &status))
+                }
+                None => Err(PyErr::new::<pyo3::exceptions::PyStopAsyncIteration, _>(
+                    "Transaction progress finished",
+                )),
+            }
+        });
+        Ok(Some(future?.into()))
+    }
+
+    /// Wait for the transaction to be finalized and check that it succeeded.
+    ///
+    /// This consumes the remaining status updates, so the progress can't be iterated afterwards.
+    ///
+    /// Returns:
+    ///     dict: A dictionary containing the `block_hash` and `extrinsic_hash`, and the `events`
+    ///         emitted by the extrinsic.
+    ///
+    /// Raises:
+    ///     DispatchError: If the extrinsic fails on-chain.
+    ///     RuntimeError: If the transaction is dropped or invalid.
+    fn wait_for_finalized_success<'py>(&self, py: Python<'py>) -> PyResult<&'py PyAny> {
+        let progress = self.progress.clone();
+        let nonces = self.nonces.clone();
+        let account_id = self.account_id.clone();
+        future_into_py(py, async move {
+            let progress = progress.lock().await.take().ok_or_else(|| {
+                PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+                    "Transaction progress already consumed",
+                )
+            })?;
+            let events = wait_for_success(progress, &nonces, account_id.as_ref()).await?;
+            Python::with_gil(|py| Ok(extrinsic_events_to_py_dict(py, &events)?.to_object(py)))
+        })
+    }
+}
+
+/// Wait for a transaction to be finalized and check that it succeeded.
+async fn wait_for_success(
+    progress: TxProgress<ChainConfig, OnlineClient<ChainConfig>>,
+    nonces: &NonceManager,
+    account_id: Option<&ChainAccountId>,
+) -> PyResult<ExtrinsicEvents<ChainConfig>> {
+    match progress.wait_for_finalized_success().await {
+        Ok(events) => Ok(events),
+        Err(e) => {
+            // A dispatch error means the extrinsic made it into a block and used its nonce
+            let nonce_unused = !matches!(e, subxt::Error::Runtime(_));
+            if let Some(account_id) = account_id.filter(|_| nonce_unused) {
+                nonces.resync(account_id).await;
+            }
+            Err(subxt_error_to_py(e))
+        }
+    }
+}
+
+/// Convert the events of a finalized extrinsic to a Python dictionary with the `block_hash`,
+/// `extrinsic_hash` and `events`.
+fn extrinsic_events_to_py_dict<'py>(
+    py: Python<'py>,
+    events: &ExtrinsicEvents<ChainConfig>,
+) -> PyResult<&'py PyDict> {
+    let dict = PyDict::new(py);
+    dict.set_item("block_hash", format!("{:?}", events.block_hash()))?;
+    dict.set_item("extrinsic_hash", format!("{:?}", events.extrinsic_hash()))?;
+    let py_events = events
+        .iter()
+        .map(|event| {
+            let event = event
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+            event_to_py_object(py, &event)
+        })
+        .collect::<PyResult<Vec<PyObject>>>()?;
+    dict.set_item("events", py_events)?;
+    Ok(dict)
+}
+
+create_exception!(
+    subxtpy,
+    DispatchError,
+    pyo3::exceptions::PyRuntimeError,
+    "An extrinsic failed on-chain.\n\nThe error is decoded from the `System.ExtrinsicFailed` event using the runtime\nmetadata. `pallet`, `pallet_index` and `error_bytes` are None for errors that don't come\nfrom a pallet, such as `BadOrigin`."
+);
+
+/// Convert a subxt error to a Python exception, raising `DispatchError` for failed extrinsics.
+fn subxt_error_to_py(error: subxt::Error) -> PyErr {
+    match error {
+        subxt::Error::Runtime(dispatch_error) => dispatch_error_to_py(&dispatch_error),
+        e => PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()),
+    }
+}
+
+/// Convert a runtime dispatch error to a `DispatchError` exception.
+///
+/// The exception has `pallet`, `error` and `docs` attributes describing the error, and the raw
+/// `pallet_index` and 4 `error_bytes` of module errors.
+fn dispatch_error_to_py(error: &subxt::error::DispatchError) -> PyErr {
+    let (pallet, name, docs, raw) = match error {
+        subxt::error::DispatchError::Module(module_error) => {
+            let raw = Some(module_error.bytes());
+            match module_error.details() {
+                Ok(details) => (
+                    Some(details.pallet.name().to_string()),
+                    details.variant.name.clone(),
+                    details.variant.docs.join("\n"),
+                    raw,
+                ),
+                Err(_) => (None, "Unknown".to_string(), String::new(), raw),
+            }
+        }
+        other => (None, format!("{:?}", other), String::new(), None),
+    };
+
+    Python::with_gil(|py| {
+        let err = DispatchError::new_err(error.to_string());
+        let value = err.value(py);
+        let attributes: [(&str, PyObject); 5] = [
+            ("pallet", pallet.to_object(py)),
+            ("error", name.to_object(py)),
+            ("docs", docs.to_object(py)),
+            ("pallet_index", raw.map(|raw| raw[0]).to_object(py)),
+            (
+                "error_bytes",
+                raw.map(|raw| PyBytes::new(py, &raw[1..])).to_object(py),
+            ),
+        ];
+        for (name, attribute) in attributes {
+            if let Err(e) = value.setattr(name, attribute) {
+                return e;
+            }
+        }
+        err
+    })
+}
+
+/// Convert a transaction status update to a Python dictionary.
+fn tx_status_to_py_object(
+    py: Python,
+    status: &TxStatus<ChainConfig, OnlineClient<ChainConfig>>,
+) -> PyResult<PyObject> {
+    let dict = PyDict::new(py);
+    match status {
+        TxStatus::Validated => dict.set_item("status", "Validated")?,
+        TxStatus::Broadcasted { num_peers } => {
+            dict.set_item("status", "Broadcast")?;
+            dict.set_item("num_peers", num_peers)?;
+        }
+        TxStatus::NoLongerInBestBlock => dict.set_item("status", "NoLongerInBestBlock")?,
+        TxStatus::InBestBlock(in_block) | TxStatus::InFinalizedBlock(in_block) => {
+            let name = match status {
+                TxStatus::InBestBlock(_) => "InBestBlock",
+                _ => "InFinalizedBlock",
+            };
+            dict.set_item("status", name)?;
+            dict.set_item("block_hash", format!("{:?}", in_block.block_hash()))?;
+            dict.set_item("extrinsic_hash", format!("{:?}", in_block.extrinsic_hash()))?;
+        }
+        TxStatus::Error { message } => {
+            dict.set_item("status", "Error")?;
+            dict.set_item("message", message)?;
+        }
+        TxStatus::Invalid { message } => {
+            dict.set_item("status", "Invalid")?;
+            dict.set_item("message", message)?;
+        }
+        TxStatus::Dropped { message } => {
+            dict.set_item("status", "Dropped")?;
+            dict.set_item("message", message)?;
+        }
+    }
+    Ok(dict.to_object(py))
+}
+
+/// A transaction that has been built but not yet signed.
+///
+/// This is returned by `SubxtClient.create_partial` for signing on another device, e.g. a cold
+/// wallet. Sign the `signer_payload` bytes and pass the signature to
+/// `SubxtClient.attach_signature`.
+#[pyclass(name = "PartialExtrinsic")]
+struct PyPartialExtrinsic {
+    partial: PartialExtrinsic<ChainConfig, OnlineClient<ChainConfig>>,
+    account_id: ChainAccountId,
+    breakdown: serde_json::Value,
+}
+
+#[pymethods]
+impl PyPartialExtrinsic {
+    /// The exact bytes to sign.
+    ///
+    /// Payloads longer than 256 bytes are already hashed with blake2-256, as the runtime expects.
+    #[getter]
+    fn signer_payload<'py>(&self, py: Python<'py>) -> &'py PyBytes {
+        PyBytes::new(py, &self.partial.signer_payload())
+    }
+
+    /// The SCALE-encoded call data.
+    #[getter]
+    fn call_data<'py>(&self, py: Python<'py>) -> &'py PyBytes {
+        PyBytes::new(py, self.partial.call_data())
+    }
+
+    /// Return a human-readable breakdown of what is being signed.
+    ///
+    /// Returns:
+    ///     str: A JSON object with the `pallet`, `call` and `args` of the transaction, the
+    ///         `call_data`, the signer's `account_id`, the `nonce`, `tip`, `tip_asset_id` and
+    ///         `mortality_blocks`, the `spec_version`, `transaction_version` and `genesis_hash`
+    ///         of the chain, and the `signer_payload`. Bytes are hex-encoded.
+    fn to_json(&self) -> String {
+        let mut breakdown = self.breakdown.clone();
+        breakdown["signer_payload"] =
+            format!("0x{}", hex::encode(self.partial.signer_payload())).into();
+        serde_json::to_string_pretty(&breakdown).expect("JSON values always serialize; qed")
+    }
+}
+
+/// A call to a pallet's extrinsic, built without submitting it.
+///
+/// Calls can be passed wherever a payload argument expects a `RuntimeCall`, e.g. to
+/// `Sudo.sudo`, and combined with `SubxtClient.batch`.
+///
+/// .. code-block:: python
+///
+///     call = Call("Balances", "transfer_keep_alive", [bob.account_id, 1_000])
+///     await client.sign_and_submit(alice, "Sudo", "sudo", [call])
+#[pyclass]
+#[derive(Clone)]
+struct Call {
+    pallet_name: String,
+    call_name: String,
+    args: Vec<PyObject>,
+}
+
+#[pymethods]
+impl Call {
+    /// Create a new Call.
+    ///
+    /// Args:
+    ///     pallet_name (str): The name of the pallet.
+    ///     call_name (str): The name of the extrinsic.
+    ///     args (list): A list of arguments for the extrinsic, which may themselves be Calls.
+    ///
+    /// Returns:
+    ///     Call: A new call.
+    ///
+    /// Raises:
+    ///     TypeError: If an argument has an unsupported type.
+    #[new]
+    fn new(pallet_name: String, call_name: String, args: &PyList) -> PyResult<Self> {
+        // Check the arguments now, though they're only encoded once the chain is known
+        for item in args.iter() {
+            py_object_to_value(item, AddressUse::Extrinsic, false)?;
+        }
+        Ok(Call {
+            pallet_name,
+            call_name,
+            args: args.iter().map(|item| item.into()).collect(),
+        })
+    }
+
+    /// The name of the pallet.
+    #[getter]
+    fn pallet_name(&self) -> String {
+        self.pallet_name.clone()
+    }
+
+    /// The name of the extrinsic.
+    #[getter]
+    fn call_name(&self) -> String {
+        self.call_name.clone()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Call({}.{})", self.pallet_name, self.call_name)
+    }
+}
+
+impl Call {
+    /// The call as a `RuntimeCall` value, for a chain with 20- or 32-byte account ids.
+    fn value(&self, py: Python, account_id20: bool) -> PyResult<Value> {
+        let args = self
+            .args
+            .iter()
+            .map(|item| py_object_to_value(item.as_ref(py), AddressUse::Extrinsic, account_id20))
+            .collect::<PyResult<Vec<Value>>>()?;
+        Ok(call_value(&self.pallet_name, &self.call_name, args))
+    }
+}
+
+/// Look up a storage entry in the runtime metadata.
+fn storage_entry<'a>(
+    metadata: &'a subxt::Metadata,
+    pallet_name: &str,
+    entry_name: &str,
+) -> PyResult<&'a StorageEntryMetadata> {
+    metadata
+        .pallet_by_name(pallet_name)
+        .and_then(|pallet| pallet.storage())
+        .and_then(|storage| storage.entry_by_name(entry_name))
+        .ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Storage entry {}.{} not found",
+                pallet_name, entry_name
+            ))
+        })
+}
+
+/// Fetch the values of storage keys with `state_queryStorageAt`, in chunks.
+///
+/// Absent keys map to None.
+async fn query_storage_at(
+    rpc: &LegacyRpcMethods<ChainConfig>,
+    keys: &[Vec<u8>],
+    block_hash: H256,
+) -> PyResult<HashMap<Vec<u8>, Option<Vec<u8>>>> {
+    let mut values = HashMap::new();
+    for chunk in keys.chunks(STORAGE_QUERY_CHUNK_SIZE) {
+        let change_sets = rpc
+            .state_query_storage_at(chunk.iter().map(|key| &key[..]), Some(block_hash))
+            .await
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+        for change_set in change_sets {
+            for (key, data) in change_set.changes {
+                values.insert(key.0, data.map(|data| data.0));
+            }
+        }
+    }
+    Ok(values)
+}
+
+/// A block to query at, passed as the `at=` argument.
+enum BlockId {
+    Hash(H256),
+    Number(u64),
+}
+
+impl BlockId {
+    /// Accept a block hash as a hex string or bytes, or a block number.
+    fn extract(at: &PyAny) -> PyResult<Self> {
+        // `bool` subclasses `int`, so `at=True` would otherwise mean block 1
+        if at.is_instance_of::<PyBool>() {
+            Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+                "A block must be given as a hash or a number",
+            ))
+        } else if let Ok(bytes) = at.downcast::<PyBytes>() {
+            let hash: [u8; 32] = bytes.as_bytes().try_into().map_err(|_| {
+                PyErr::new::<pyo3::exceptions::PyValueError, _>("A block hash must be 32 bytes")
+            })?;
+            Ok(BlockId::Hash(H256(hash)))
+        } else if let Ok(hash) = at.downcast::<PyString>() {
+            Ok(BlockId::Hash(parse_hash(hash.to_str()?)?))
+        } else if let Ok(number) = at.extract::<u64>() {
+            Ok(BlockId::Number(number))
+        } else {
+            Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+                "A block must be given as a hash or a number",
+            ))
+        }
+    }
+}
+
+/// Resolve the `at=` argument to a block hash, defaulting to the latest finalized block.
+async fn resolve_block(
+    api: &OnlineClient<ChainConfig>,
+    rpc: &LegacyRpcMethods<ChainConfig>,
+    at: Option<BlockId>,
+) -> PyResult<H256> {
+    match at {
+        Some(BlockId::Hash(hash)) => rpc
+            .chain_get_header(Some(hash))
+            .await
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?
+            .map(|_| hash)
+            .ok_or_else(|| {
+                PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Block {:?} not found",
+                    hash
+                ))
+            }),
+        Some(BlockId::Number(number)) => rpc
+            .chain_get_block_hash(Some(number.into()))
+            .await
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?
+            .ok_or_else(|| {
+                PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Block {} not found",
+                    number
+                ))
+            }),
+        None => Ok(api
+            .blocks()
+            .at_latest()
+            .await
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?
+            .hash()),
+    }
+}
+
+/// A client for interacting with a Substrate-based blockchain.
+///
+/// This class provides methods to interact with the blockchain, including fetching storage entries,
+/// constants, events, making runtime API calls, and submitting transactions.
+///
+/// Both Substrate chains and Ethereum-style (AccountId20) EVM parachains are supported; the
+/// address and signature encoding used for a transaction follows the signing Keypair's scheme.
+///
+/// The client hands out sequential nonces per account, so transactions from one account can be
+/// submitted concurrently without colliding.
+#[pyclass]
+struct SubxtClient {
+    api: Arc<OnlineClient<ChainConfig>>,
+    /// Legacy RPC methods, for queries the subxt client doesn't expose.
+    rpc: LegacyRpcMethods<ChainConfig>,
+    nonces: Arc<NonceManager>,
+    /// Whether the chain uses Ethereum-style 20-byte account ids.
+    account_id20: bool,
+}
+
+impl SubxtClient {
+    async fn connect(url: &str) -> PyResult<Self> {
+        let rpc_client = RpcClient::from_url(url)
+            .await
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+        let api = OnlineClient::<ChainConfig>::from_rpc_client(rpc_client.clone())
+            .await
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+        let rpc = LegacyRpcMethods::new(rpc_client);
+        Ok(SubxtClient {
+            account_id20: uses_account_id20(&api.metadata()),
+            api: Arc::new(api),
+            nonces: Arc::new(NonceManager::new(rpc.clone())),
+            rpc,
+        })
+    }
+}
+
+#[pymethods]
+impl SubxtClient {
+    /// Create a new SubxtClient instance asynchronously.
+    ///
+    /// Returns:
+    ///     SubxtClient: A new client connected to the default network.
+    ///
+    /// Raises:
+    ///     RuntimeError: If the client fails to connect.
+    #[staticmethod]
+    #[pyo3(name = "new")]
+    fn py_new(py: Python<'_>) -> PyResult<&PyAny> {
+        future_into_py(py, async { SubxtClient::connect(DEFAULT_URL).await })
+    }
+
+    /// Create a new SubxtClient instance from a URL asynchronously.
+    ///
+    /// Args:
+    ///     url (str): The URL of the node to connect to.
+    ///
+    /// Returns:
+    ///     SubxtClient: A new client connected to the specified node.
+    ///
+    /// Raises:
+    ///     RuntimeError: If the client fails to connect.
+    #[staticmethod]
+    #[pyo3(name = "from_url")]
+    fn from_url(py: Python<'_>, url: String) -> PyResult<&PyAny> {
+        future_into_py(py, async move { SubxtClient::connect(&url).await })
+    }
+
+    /// Fetch a storage entry from the blockchain asynchronously.
+    ///
+    /// Absent entries raise a ValueError; use `storage_or_default` to get their default instead.
+    ///
+    /// Args:
+    ///     pallet_name (str): The name of the pallet.
+    ///     entry_name (str): The name of the storage entry.
+    ///     key (list): A list of keys for the storage entry.
+    ///     at (str | bytes | int, optional): The block to query at, as a block hash or number.
+    ///         Defaults to the latest finalized block.
+    ///
+    /// Returns:
+    ///     Any: The value of the storage entry.
+    ///
+    /// Raises:
+    ///     RuntimeError: If fetching the storage entry fails.
+    ///     ValueError: If the storage entry or block is not found.
+    #[pyo3(signature = (pallet_name, entry_name, key, at=None))]
+    fn storage<'py>(
+        &self,
+        py: Python<'py>,
+        pallet_name: String,
+        entry_name: String,
+        key: &PyList,
+        at: Option<&PyAny>,
+    ) -> PyResult<&'py PyAny> {
+        let api = self.api.clone();
+        let rpc = self.rpc.clone();
+        let values: Vec<Value> = key
+            .iter()
+            .map(|item| py_object_to_value(item, AddressUse::Storage, self.account_id20))
+            .collect::<PyResult<Vec<Value>>>()?;
+        let at = at.map(BlockId::extract).transpose()?;
+        future_into_py(py, async move {
+            let storage_query = subxt::dynamic::storage(pallet_name, entry_name, values);
+            let block_hash = resolve_block(&api, &rpc, at).await?;
+            let result = api
+                .storage()
+                .at(block_hash)
+                .fetch(&storage_query)
+                .await
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+            match result {
+                Some(value) => {
+                    let decoded = value.to_value().map_err(|e| {
+                        PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string())
+                    })?;
+                    let py_value = Python::with_gil(|py| decoded_value_to_py_object(py, &decoded))?;
+                    Ok(py_value)
+                }
+                None => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "Storage not found",
+                )),
+            }
+        })
+    }
+
+    /// Fetch a storage entry, falling back to its default if it's absent, asynchronously.
+    ///
+    /// Entries declared with `ValueQuery` fall back to the default value in the runtime metadata,
+    /// e.g. an empty `System.Account` for a fresh account. Entries declared with `OptionQuery`
+    /// have no default, so None is returned for them instead.
+    ///
+    /// Args:
+    ///     pallet_name (str): The name of the pallet.
+    ///     entry_name (str): The name of the storage entry.
+    ///     key (list): A list of keys for the storage entry.
+    ///     at (str | bytes | int, optional): The block to query at, as a block hash or number.
+    ///         Defaults to the latest finalized block.
+    ///
+    /// Returns:
+    ///     Any: The value of the storage entry, its default, or None.
+    ///
+    /// Raises:
+    ///     RuntimeError: If fetching the storage entry fails.
+    ///     ValueError: If the storage entry isn't in the metadata, or the block is not found.
+    #[pyo3(signature = (pallet_name, entry_name, key, at=None))]
+    fn storage_or_default<'py>(
+        &self,
+        py: Python<'py>,
+        pallet_name: String,
+        entry_name: String,
+        key: &PyList,
+        at: Option<&PyAny>,
+    ) -> PyResult<&'py PyAny> {
+        let api = self.api.clone();
+        let rpc = self.rpc.clone();
+        let values: Vec<Value> = key
+            .iter()
+            .map(|item| py_object_to_value(item, AddressUse::Storage, self.account_id20))
+            .collect::<PyResult<Vec<Value>>>()?;
+        let at = at.map(BlockId::extract).transpose()?;
+        let modifier = storage_entry(&self.api.metadata(), &pallet_name, &entry_name)?.modifier();
+        future_into_py(py, async move {
+            let storage_query = subxt::dynamic::storage(pallet_name, entry_name, values);
+            let block_hash = resolve_block(&api, &rpc, at).await?;
+            let storage = api.storage().at(block_hash);
+            let result = match modifier {
+                StorageEntryModifier::Default => {
+                    Some(storage.fetch_or_default(&storage_query).await)
+                }
+                StorageEntryModifier::Optional => storage.fetch(&storage_query).await.transpose(),
+            };
+
+            match result {
+                Some(value) => {
+                    let decoded = value
+                        .map_err(|e| {
+                            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string())
+                        })?
+                        .to_value()
+                        .map_err(|e| {
+                            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string())
+                        })?;
+                    Python::with_gil(|py| decoded_value_to_py_object(py, &decoded))
+                }
+                None => Ok(Python::with_gil(|py| py.None())),
+            }
+        })
+    }
+
+    /// Fetch many entries of a storage map in one go, asynchronously.
+    ///
+    /// The keys are queried with `state_queryStorageAt`, in chunks to keep requests small. As for
+    /// `storage_or_default`, absent entries declared with `ValueQuery` fall back to their default
+    /// value, and absent `OptionQuery` entries are None.
+    ///
+    /// Args:
+    ///     pallet_name (str): The name of the pallet.
+    ///     entry_name (str): The name of the storage entry.
+    ///     keys_list (list): A list of keys, each a list of keys for the storage entry as for
+    ///         `storage`.
+    ///     at (str | bytes | int, optional): The block to query at, as a block hash or number.
+    ///         Defaults to the latest finalized block.
+    ///
+    /// Returns:
+    ///     list: The value of each storage entry, in the order of `keys_list`, its default, or
+    ///         None.
+    ///
+    /// Raises:
+    ///     RuntimeError: If fetching or decoding the storage entries fails.
+    ///     ValueError: If the storage entry isn't in the metadata, or the block is not found.
+    #[pyo3(signature = (pallet_name, entry_name, keys_list, at=None))]
+    fn storage_multi<'py>(
+        &self,
+        py: Python<'py>,
+        pallet_name: String,
+        entry_name: String,
+        keys_list: &PyList,
+        at: Option<&PyAny>,
+    ) -> PyResult<&'py PyAny> {
+        let api = self.api.clone();
+        let rpc = self.rpc.clone();
+        let metadata = self.api.metadata();
+        let entry = storage_entry(&metadata, &pallet_name, &entry_name)?;
+        let value_ty = entry.entry_type().value_ty();
+        let default = match entry.modifier() {
+            StorageEntryModifier::Default => Some(entry.default_bytes().to_vec()),
+            StorageEntryModifier::Optional => None,
+        };
+        let storage_keys = keys_list
+            .iter()
+            .map(|key| {
+                let values = key
+                    .downcast::<PyList>()?
+                    .iter()
+                    .map(|item| py_object_to_value(item, AddressUse::Storage, self.account_id20))
+                    .collect::<PyResult<Vec<Value>>>()?;
+                let storage_query = subxt::dynamic::storage(&pallet_name, &entry_name, values);
+                api.storage()
+                    .address_bytes(&storage_query)
+                    .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
+            })
+            .collect::<PyResult<Vec<Vec<u8>>>>()?;
+        let at = at.map(BlockId::extract).transpose()?;
+        future_into_py(py, async move {
+            let block_hash = resolve_block(&api, &rpc, at).await?;
+            let changes = query_storage_at(&rpc, &storage_keys, block_hash).await?;
+            let values = storage_keys
+                .iter()
+                .map(|key| {
+                    let data = match changes.get(key) {
+                        Some(Some(data)) => data,
+                        _ => match &default {
+                            Some(default) => default,
+                            None => return Ok(None),
+                        },
+                    };
+                    let mut cursor = &data[..];
+                    let value =
+                        decode_as_type(&mut cursor, value_ty, metadata.types()).map_err(|e| {
+                            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string())
+                        })?;
+                    if !cursor.is_empty() {
+                        return Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
+                            "{} bytes left over after decoding a {}.{} value",
+                            cursor.len(),
+                            pallet_name,
+                            entry_name
+                        )));
+                    }
+                    Ok(Some(value))
+                })
+                .collect::<PyResult<Vec<Option<Value<u32>>>>>()?;
+            Python::with_gil(|py| -> PyResult<PyObject> {
+                let py_values = values
+                    .iter()
+                    .map(|value| match value {
+                        Some(value) => decoded_value_to_py_object(py, value),
+                        None => Ok(py.None()),
+                    })
+                    .collect::<PyResult<Vec<PyObject>>>()?;
+                Ok(PyList::new(py, py_values).to_object(py))
+            })
+        })
+    }
+
+    /// Return the SS58 address prefix of the connected chain.
+    ///
+    /// The prefix is read from the `System.SS58Prefix` constant in the runtime metadata.
+    ///
+    /// Returns:
+    ///     int: The chain's SS58 prefix, or 42 (generic Substrate) if the runtime doesn't define one.
+    fn ss58_prefix(&self) -> u16 {
+        let constant_query = subxt::dynamic::constant("System", "SS58Prefix");
+        self.api
+            .constants()
+            .at(&constant_query)
+            .ok()
+            .and_then(|value| value.to_value().ok())
+            .and_then(|value| value.as_u128())
+            .map(|prefix| prefix as u16)
+            .unwrap_or(42)
+    }
+
+    /// Fetch a constant value from the blockchain asynchronously.
+    ///
+    /// Args:
+    ///     pallet_name (str): The name of the pallet.
+    ///     constant_name (str): The name of the constant.
+    ///
+    /// Returns:
+    ///     Any: The value of the constant.
+    ///
+    /// Raises:
+    ///     RuntimeError: If fetching the constant fails.
+    fn constant<'py>(
+        &self,
+        py: Python<'py>,
+        pallet_name: String,
+        constant_name: String,
+    ) -> PyResult<&'py PyAny> {
+        let api = self.api.clone();
+        future_into_py(py, async move {
+            let constant_query = subxt::dynamic::constant(pallet_name, constant_name);
+
+            let value = api
+                .constants()
+                .at(&constant_query)
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+            let decoded = value
+                .to_value()
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+            let py_value = Python::with_gil(|py| decoded_value_to_py_object(py, &decoded))?;
+            Ok(py_value)
+        })
+    }
+
+    /// Fetch events from the blockchain asynchronously.
+    ///
+    /// Args:
+    ///     at (str | bytes | int, optional): The block to query at, as a block hash or number.
+    ///         Defaults to the latest finalized block.
+    ///
+    /// Returns:
+    ///     list: A list of events, where each event is a dictionary containing pallet, variant, and fields.
+    ///
+    /// Raises:
+    ///     RuntimeError: If fetching events fails.
+    ///     ValueError: If the block is not found.
+    #[pyo3(signature = (at=None))]
+    fn events<'py>(&self, py: Python<'py>, at: Option<&PyAny>) -> PyResult<&'py PyAny> {
+        let api = self.api.clone();
+        let rpc = self.rpc.clone();
+        let at = at.map(BlockId::extract).transpose()?;
+        future_into_py(py, async move {
+            let block_hash = resolve_block(&api, &rpc, at).await?;
+            let events =
+                api.events().at(block_hash).await.map_err(|e| {
+                    PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string())
+                })?;
+
+            let events_vec: Vec<_> = events
+                .iter()
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+            Python::with_gil(|py| -> PyResult<PyObject> {
+                let py_events = events_vec
+                    .iter()
+                    .map(|event| event_to_py_object(py, event))
+                    .collect::<PyResult<Vec<PyObject>>>()?;
+                Ok(PyList::new(py, py_events).to_object(py))
+            })
+        })
+    }
+
+    /// Perform a runtime API call to the blockchain asynchronously.
+    ///
+    /// Args:
+    ///     pallet_name (str): The name of the pallet.
+    ///     entry_name (str): The name of the runtime API function.
+    ///     key (list): A list of arguments for the runtime API call.
+    ///     at (str | bytes | int, optional): The block to query at, as a block hash or number.
+    ///         Defaults to the latest finalized block.
+    ///
+    /// Returns:
+    ///     Any: The result of the runtime API call.
+    ///
+    /// Raises:
+    ///     RuntimeError: If the runtime API call fails.
+    ///     ValueError: If the block is not found.
+    #[pyo3(signature = (pallet_name, entry_name, key, at=None))]
+    fn runtime_api_call<'py>(
+        &self,
+        py: Python<'py>,
+        pallet_name: String,
+        entry_name: String,
+        key: &PyList,
+        at: Option<&PyAny>,
+    ) -> PyResult<&'py PyAny> {
+        let api = self.api.clone();
+        let rpc = self.rpc.clone();
+        let values: Vec<Value> = key
+            .iter()
+            .map(|item| py_object_to_value(item, AddressUse::Storage, self.account_id20))
+            .collect::<PyResult<Vec<Value>>>()?;
+        let at = at.map(BlockId::extract).transpose()?;
+        future_into_py(py, async move {
+            let runtime_api_call =
+                subxt::dynamic::runtime_api_call(&pallet_name, &entry_name, values);
+
+            let block_hash = resolve_block(&api, &rpc, at).await?;
+            let result = api
+                .runtime_api()
+                .at(block_hash)
+                .call(runtime_api_call)
+                .await
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+            let decoded = result
+                .to_value()
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+            let py_value = Python::with_gil(|py| decoded_value_to_py_object(py, &decoded))?;
+            Ok(py_value)
+        })
+    }
+
+    /// Iterate over storage entries from the blockchain asynchronously.
+    ///
+    /// Args:
+    ///     pallet_name (str): The name of the pallet.
+    ///     entry_name (str): The name of the storage entry.
+    ///     key (list): The leading keys of the storage map to iterate under, converted like the
+    ///         keys passed to `storage`. Pass a prefix of an N-map's keys, e.g. just the era for
+    ///         `Staking.ErasStakers`, or an empty list to iterate the whole map. A single key may
+    ///         also be passed as bytes.
+    ///     at (str | bytes | int, optional): The block to query at, as a block hash or number.
+    ///         Defaults to the latest finalized block.
+    ///     page_size (int, optional): The number of keys fetched per request. Defaults to 32.
+    ///     start_key (bytes, optional): Resume iterating after this key, e.g. a saved
+    ///         `StorageIterator.last_key`. It must lie under the iterated prefix. Pass the same
+    ///         `at` block to see consistent state.
+    ///
+    /// Returns:
+    ///     StorageIterator: An asynchronous iterator over storage key-value pairs.
+    ///
+    /// Raises:
+    ///     RuntimeError: If the iteration fails.
+    ///     ValueError: If the storage entry isn't in the metadata, the block is not found,
+    ///         `page_size` is zero, or `start_key` doesn't start with the iterated prefix.
+    #[pyo3(signature = (pallet_name, entry_name, key, at=None, page_size=STORAGE_PAGE_SIZE, start_key=None))]
+    fn storage_iter<'py>(
+        &self,
+        py: Python<'py>,
+        pallet_name: String,
+        entry_name: String,
+        key: &PyAny,
+        at: Option<&PyAny>,
+        page_size: u32,
+        start_key: Option<Vec<u8>>,
+    ) -> PyResult<&'py PyAny> {
+        let api = self.api.clone();
+        let rpc = self.rpc.clone();
+        let values: Vec<Value> = if let Ok(bytes) = key.downcast::<PyBytes>() {
+            match bytes.as_bytes() {
+                [] => vec![],
+                bytes => vec![Value::from_bytes(bytes)],
+            }
+        } else {
+            key.downcast::<PyList>()?
+                .iter()
+                .map(|item| py_object_to_value(item, AddressUse::Storage, self.account_id20))
+                .collect::<PyResult<Vec<Value>>>()?
+        };
+        if page_size == 0 {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "page_size must be positive",
+            ));
+        }
+        let metadata = self.api.metadata();
+        let (keys, value_ty) =
+            match storage_entry(&metadata, &pallet_name, &entry_name)?.entry_type() {
+                StorageEntryType::Plain(value_ty) => (vec![], *value_ty),
+                StorageEntryType::Map {
+                    hashers,
+                    key_ty,
+                    value_ty,
+                } => {
+                    // Maps with several keys have a tuple of their key types
+                    let key_tys = match &metadata.types().resolve(*key_ty).map(|ty| &ty.type_def) {
+                        Some(TypeDef::Tuple(tuple)) if hashers.len() > 1 => {
+                            tuple.fields.iter().map(|field| field.id).collect()
+                        }
+                        _ => vec![*key_ty],
+                    };
+                    (hashers.iter().cloned().zip(key_tys).collect(), *value_ty)
+                }
+            };
+        let storage_query = subxt::dynamic::storage(pallet_name, entry_name, values);
+        let prefix = api
+            .storage()
+            .address_bytes(&storage_query)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+        if matches!(&start_key, Some(start_key) if !start_key.starts_with(&prefix)) {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "start_key is not under the iterated storage prefix",
+            ));
+        }
+        let at = at.map(BlockId::extract).transpose()?;
+        future_into_py(py, async move {
+            let block_hash = resolve_block(&api, &rpc, at).await?;
+            let pages = StoragePages {
+                rpc,
+                metadata,
+                block_hash,
+                prefix,
+                page_size,
+                keys,
+                value_ty,
+                cursor: start_key,
+                buffer: VecDeque::new(),
+                done: false,
+            };
+            Ok(StorageIterator {
+                pages: Arc::new(tokio::sync::Mutex::new(pages)),
+                last_key: Arc::new(std::sync::Mutex::new(None)),
+            })
+        })
+    }
+
+    /// Sign and submit a transaction to the blockchain asynchronously.
+    ///
+    /// Instead of a `Keypair`, any object with an `account_id()` method and a synchronous
+    /// `sign(payload: bytes) -> bytes` method can sign the transaction, so that private keys can
+    /// stay in a KMS or HSM. It may also have a `scheme` attribute ("sr25519", "ed25519", "ecdsa"
+    /// or "ethereum") describing the signatures it produces.
+    ///
+    /// Args:
+    ///     from (Keypair | object): The keypair or external signer to sign the transaction.
+    ///     pallet_name (str): The name of the pallet.
+    ///     entry_name (str): The name of the extrinsic.
+    ///     payload (list): A list of arguments for the extrinsic.
+    ///     **params: Optional transaction parameters:
+    ///         tip (int): A tip for the block author, to prioritize the transaction.
+    ///         tip_asset_id (int): Pay the tip (and fee) in this asset, on chains with
+    ///             `ChargeAssetTxPayment`.
+    ///         mortality_blocks (int): Make the transaction mortal, valid for this many blocks
+    ///             from the latest block. Transactions are immortal by default.
+    ///         nonce (int): Use this nonce instead of the one from the client's nonce manager.
+    ///         extras (dict): Values for chain-specific transaction extensions by name, such as
+    ///             `{"CheckAppId": 1}` on Avail, encoded against the types in the metadata.
+    ///
+    /// Returns:
+    ///     str: The transaction hash as a hexadecimal string.
+    ///
+    /// Raises:
+    ///     RuntimeError: If signing or submitting the transaction fails.
+    ///     ValueError: If an external signer returns an invalid account id or signature.
+    ///     TypeError: If an unknown transaction parameter is passed.
+    #[pyo3(signature = (from, pallet_name, entry_name, payload, **params))]
+    fn sign_and_submit<'py>(
+        &self,
+        py: Python<'py>,
+        from: &PyAny,
+        pallet_name: String,
+        entry_name: String,
+        payload: &PyList,
+        params: Option<&PyDict>,
+    ) -> PyResult<&'py PyAny> {
+        let api = self.api.clone();
+        let nonces = self.nonces.clone();
+        let values: Vec<Value> = payload
+            .iter()
+            .map(|item| py_object_to_value(item, AddressUse::Extrinsic, self.account_id20))
+            .collect::<PyResult<Vec<Value>>>()?;
+        let options = TxOptions::from_kwargs(params, self.account_id20)?;
+        let signer = TxSigner::extract(from)?;
+        future_into_py(py, async move {
+            let tx_payload = tx(pallet_name, entry_name, values);
+            submit_signed(&api, &nonces, &tx_payload, &signer, &options).await
+        })
+    }
+
+    /// Sign and submit a transaction, and watch its progress asynchronously.
+    ///
+    /// Args:
+    ///     from (Keypair | object): The keypair or external signer to sign the transaction.
+    ///     pallet_name (str): The name of the pallet.
+    ///     entry_name (str): The name of the extrinsic.
+    ///     payload (list): A list of arguments for the extrinsic.
+    ///     **params: Optional transaction parameters, as for `sign_and_submit`.
+    ///
+    /// Returns:
+    ///     TransactionProgress: An asynchronous iterator over the transaction's status updates.
+    ///
+    /// Raises:
+    ///     RuntimeError: If signing or submitting the transaction fails.
+    ///     ValueError: If an external signer returns an invalid account id or signature.
+    #[pyo3(signature = (from, pallet_name, entry_name, payload, **params))]
+    fn sign_and_submit_and_watch<'py>(
+        &self,
+        py: Python<'py>,
+        from: &PyAny,
+        pallet_name: String,
+        entry_name: String,
+        payload: &PyList,
+        params: Option<&PyDict>,
+    ) -> PyResult<&'py PyAny> {
+        let api = self.api.clone();
+        let nonces = self.nonces.clone();
+        let values: Vec<Value> = payload
+            .iter()
+            .map(|item| py_object_to_value(item, AddressUse::Extrinsic, self.account_id20))
+            .collect::<PyResult<Vec<Value>>>()?;
+        let options = TxOptions::from_kwargs(params, self.account_id20)?;
+        let signer = TxSigner::extract(from)?;
+        future_into_py(py, async move {
+            let tx_payload = tx(pallet_name, entry_name, values);
+            let progress = submit_and_watch(&api, &nonces, &tx_payload, &signer, &options).await?;
+            Ok(TransactionProgress::new(
+                progress,
+                nonces,
+                Some(signer.account_id()),
+            ))
+        })
+    }
+
+    /// Sign a transaction without submitting it, asynchronously.
+    ///
+    /// The nonce is taken from the client's nonce manager unless given, so several transactions
+    /// from one account can be signed ahead of time and broadcast later with `submit_raw`.
+    ///
+    /// Args:
+    ///     from (Keypair | object): The keypair or external signer to sign the transaction.
+    ///     pallet_name (str): The name of the pallet.
+    ///     entry_name (str): The name of the extrinsic.
+    ///     payload (list): A list of arguments for the extrinsic.
+    ///     **params: Optional transaction parameters, as for `sign_and_submit`.
+    ///
+    /// Returns:
+    ///     bytes: The SCALE-encoded signed extrinsic.
+    ///
+    /// Raises:
+    ///     RuntimeError: If signing the transaction fails.
+    ///     ValueError: If an external signer returns an invalid account id or signature.
+    ///     TypeError: If an unknown transaction parameter is passed.
+    #[pyo3(signature = (from, pallet_name, entry_name, payload, **params))]
+    fn create_signed<'py>(
+        &self,
+        py: Python<'py>,
+        from: &PyAny,
+        pallet_name: String,
+        entry_name: String,
+        payload: &PyList,
+        params: Option<&PyDict>,
+    ) -> PyResult<&'py PyAny> {
+        let api = self.api.clone();
+        let nonces = self.nonces.clone();
+        let values: Vec<Value> = payload
+            .iter()
+            .map(|item| py_object_to_value(item, AddressUse::Extrinsic, self.account_id20))
+            .collect::<PyResult<Vec<Value>>>()?;
+        let options = TxOptions::from_kwargs(params, self.account_id20)?;
+        let signer = TxSigner::extract(from)?;
+        future_into_py(py, async move {
+            let tx_payload = tx(pallet_name, entry_name, values);
+            let extrinsic = create_signed(&api, &nonces, &tx_payload, &signer, &options).await?;
+            Python::with_gil(|py| Ok(PyBytes::new(py, extrinsic.encoded()).to_object(py)))
+        })
+    }
+
+    /// Build a transaction for signing elsewhere, asynchronously.
+    ///
+    /// Unless given, the nonce is the account's on-chain nonce. The client's nonce manager isn't
+    /// advanced, since the transaction might never be submitted.
+    ///
+    /// Args:
+    ///     pallet_name (str): The name of the pallet.
+    ///     entry_name (str): The name of the extrinsic.
+    ///     payload (list): A list of arguments for the extrinsic.
+    ///     account_id (bytes | str): The account that will sign the transaction, as bytes, a hex
+    ///         string or an SS58 address.
+    ///     **params: Optional transaction parameters, as for `sign_and_submit`.
+    ///
+    /// Returns:
+    ///     PartialExtrinsic: The unsigned transaction, with the `signer_payload` to sign.
+    ///
+    /// Raises:
+    ///     RuntimeError: If building the transaction fails.
+    ///     ValueError: If the account id is invalid.
+    ///     TypeError: If an unknown transaction parameter is passed.
+    #[pyo3(signature = (pallet_name, entry_name, payload, account_id, **params))]
+    fn create_partial<'py>(
+        &self,
+        py: Python<'py>,
+        pallet_name: String,
+        entry_name: String,
+        payload: &PyList,
+        account_id: &PyAny,
+        params: Option<&PyDict>,
+    ) -> PyResult<&'py PyAny> {
+        let api = self.api.clone();
+        let nonces = self.nonces.clone();
+        let values: Vec<Value> = payload
+            .iter()
+            .map(|item| py_object_to_value(item, AddressUse::Extrinsic, self.account_id20))
+            .collect::<PyResult<Vec<Value>>>()?;
+        let mut options = TxOptions::from_kwargs(params, self.account_id20)?;
+        let account_id = py_to_account_id(account_id)?;
+        future_into_py(py, async move {
+            let args: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+            let tx_payload = tx(pallet_name.as_str(), entry_name.as_str(), values);
+            if options.nonce.is_none() {
+                options.nonce = Some(account_nonce(&api, &account_id).await?);
+            }
+            let partial =
+                create_partial(&api, &nonces, &tx_payload, &account_id, &mut options).await?;
+            let runtime_version = api.runtime_version();
+            let breakdown = serde_json::json!({
+                "pallet": pallet_name,
+                "call": entry_name,
+                "args": args,
+                "call_data": format!("0x{}", hex::encode(partial.call_data())),
+                "account_id": format!("0x{}", hex::encode(account_id.encode())),
+                "nonce": options.nonce,
+                // u128 doesn't fit in a JSON number
+                "tip": options.tip.to_string(),
+                "tip_asset_id": options.tip_asset_id,
+                "mortality_blocks": options.mortality_blocks,
+                "spec_version": runtime_version.spec_version,
+                "transaction_version": runtime_version.transaction_version,
+                "genesis_hash": format!("{:?}", api.genesis_hash()),
+            });
+            Ok(PyPartialExtrinsic {
+                partial,
+                account_id,
+                breakdown,
+            })
+        })
+    }
+
+    /// Attach a signature to a partial extrinsic.
+    ///
+    /// Args:
+    ///     partial (PartialExtrinsic): The transaction returned by `create_partial`.
+    ///     signature (bytes): The signature of its `signer_payload`.
+    ///     scheme (str, optional): The scheme of the signature ("sr25519", "ed25519", "ecdsa" or
+    ///         "ethereum"). Defaults to "ethereum" for 20-byte account ids and "sr25519" otherwise.
+    ///
+    /// Returns:
+    ///     bytes: The SCALE-encoded signed extrinsic, to be submitted with `submit_raw`.
+    ///
+    /// Raises:
+    ///     ValueError: If the scheme is unknown or the signature has the wrong length for it.
+    #[pyo3(signature = (partial, signature, scheme=None))]
+    fn attach_signature<'py>(
+        &self,
+        py: Python<'py>,
+        partial: &PyPartialExtrinsic,
+        signature: &[u8],
+        scheme: Option<&str>,
+    ) -> PyResult<&'py PyBytes> {
+        let scheme = match scheme {
+            Some(scheme) => KeyScheme::parse(scheme)?,
+            None => KeyScheme::default_for(&partial.account_id),
+        };
+        let address = ChainAddress::from(partial.account_id.clone());
+        let extrinsic = partial
+            .partial
+            .sign_with_address_and_signature(&address, &scheme.signature(signature)?);
+        Ok(PyBytes::new(py, extrinsic.encoded()))
+    }
+
+    /// Submit an already signed extrinsic asynchronously.
+    ///
+    /// Args:
+    ///     extrinsic (bytes): The SCALE-encoded extrinsic, e.g. from `create_signed` or
+    ///         `OfflineClient.create_signed`.
+    ///
+    /// Returns:
+    ///     str: The transaction hash as a hexadecimal string.
+    ///
+    /// Raises:
+    ///     RuntimeError: If the node rejects the extrinsic.
+    fn submit_raw<'py>(&self, py: Python<'py>, extrinsic: Vec<u8>) -> PyResult<&'py PyAny> {
+        let api = self.api.clone();
+        future_into_py(py, async move {
+            let hash = SubmittableExtrinsic::from_bytes((*api).clone(), extrinsic)
+                .submit()
+                .await
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+            Ok(format!("{:?}", hash))
+        })
+    }
+
+    /// Submit an unsigned transaction asynchronously.
+    ///
+    /// Only calls that the runtime validates without a signature are accepted, e.g. those of
+    /// pallets with a `ValidateUnsigned` implementation.
+    ///
+    /// Args:
+    ///     pallet_name (str): The name of the pallet.
+    ///     entry_name (str): The name of the extrinsic.
+    ///     payload (list): A list of arguments for the extrinsic.
+    ///
+    /// Returns:
+    ///     str: The transaction hash as a hexadecimal string.
+    ///
+    /// Raises:
+    ///     RuntimeError: If building the transaction fails or the node rejects it.
+    fn submit_unsigned<'py>(
+        &self,
+        py: Python<'py>,
+        pallet_name: String,
+        entry_name: String,
+        payload: &PyList,
+    ) -> PyResult<&'py PyAny> {
+        let api = self.api.clone();
+        let values: Vec<Value> = payload
+            .iter()
+            .map(|item| py_object_to_value(item, AddressUse::Extrinsic, self.account_id20))
+            .collect::<PyResult<Vec<Value>>>()?;
+        future_into_py(py, async move {
+            let tx_payload = tx(pallet_name, entry_name, values);
+            let hash = api
+                .tx()
+                .create_unsigned(&tx_payload)
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?
+                .submit()
+                .await
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+            Ok(format!("{:?}", hash))
+        })
+    }
+
+    /// Submit an unsigned transaction and watch its progress asynchronously.
+    ///
+    /// Args:
+    ///     pallet_name (str): The name of the pallet.
+    ///     entry_name (str): The name of the extrinsic.
+    ///     payload (list): A list of arguments for the extrinsic.
+    ///
+    /// Returns:
+    ///     TransactionProgress: An asynchronous iterator over the transaction's status updates.
+    ///
+    /// Raises:
+    ///     RuntimeError: If building the transaction fails or the node rejects it.
+    fn submit_unsigned_and_watch<'py>(
+        &self,
+        py: Python<'py>,
+        pallet_name: String,
+        entry_name: String,
+        payload: &PyList,
+    ) -> PyResult<&'py PyAny> {
+        let api = self.api.clone();
+        let nonces = self.nonces.clone();
+        let values: Vec<Value> = payload
+            .iter()
+            .map(|item| py_object_to_value(item, AddressUse::Extrinsic, self.account_id20))
+            .collect::<PyResult<Vec<Value>>>()?;
+        future_into_py(py, async move {
+            let tx_payload = tx(pallet_name, entry_name, values);
+            let progress = api
+                .tx()
+                .create_unsigned(&tx_payload)
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?
+                .submit_and_watch()
+                .await
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+            Ok(TransactionProgress::new(progress, nonces, None))
+        })
     }
 
-    /// Return the next storage key-value pair from the iterator.
+    /// Fetch the runtime metadata of the latest block asynchronously.
     ///
-    /// Yields:
-    ///     dict: A dictionary containing the key bytes, keys, and value.
+    /// The metadata can be saved to a file and loaded into an `OfflineClient`.
+    ///
+    /// Returns:
+    ///     bytes: The SCALE-encoded metadata, preferring metadata V15 if the runtime supports it.
     ///
     /// Raises:
-    ///     StopAsyncIteration: When no more items are available.
-    fn __anext__<'a>(&self, py: Python<'a>) -> PyResult<Option<PyObject>> {
-        let results = self.results.clone();
-        let future = future_into_py(py, async move {
-            let mut results = results.lock().await;
-            if let Some(result) = results.next().await {
-                let key_val = result.map_err(|e| {
+    ///     RuntimeError: If the runtime API call fails.
+    fn metadata<'py>(&self, py: Python<'py>) -> PyResult<&'py PyAny> {
+        let api = self.api.clone();
+        future_into_py(py, async move {
+            let runtime_api =
+                api.runtime_api().at_latest().await.map_err(|e| {
                     PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string())
                 })?;
-                let py_dict = Python::with_gil(|py| -> PyResult<PyObject> {
-                    let dict = PyDict::new(py);
-                    dict.set_item("key_bytes", PyBytes::new(py, &key_val.key_bytes))?;
+            let metadata_v15 = runtime_api
+                .call_raw::<Option<Vec<u8>>>(
+                    "Metadata_metadata_at_version",
+                    Some(&METADATA_VERSION.encode()),
+                )
+                .await;
+            let metadata = match metadata_v15 {
+                Ok(Some(metadata)) => metadata,
+                // Older runtimes only provide their default metadata version
+                _ => runtime_api
+                    .call_raw::<Vec<u8>>("Metadata_metadata", None)
+                    .await
+                    .map_err(|e| {
+                        PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string())
+                    })?,
+            };
+            Python::with_gil(|py| Ok(PyBytes::new(py, &metadata).to_object(py)))
+        })
+    }
 
-                    let py_keys = PyList::new(
-                        py,
-                        key_val.keys.iter().map(|k| {
-                            let new_k = k.clone().map_context(|_| 0u32);
-                            decoded_value_to_py_object(py, &new_k).unwrap()
-                        }),
-                    );
-                    dict.set_item("keys", py_keys)?;
+    /// Return the genesis hash of the connected chain.
+    ///
+    /// Returns:
+    ///     str: The genesis hash as a hexadecimal string.
+    fn genesis_hash(&self) -> String {
+        format!("{:?}", self.api.genesis_hash())
+    }
 
-                    // Convert value to PyObject
-                    let py_value =
-                        decoded_value_to_py_object(py, &key_val.value.to_value().unwrap())?;
-                    dict.set_item("value", py_value)?;
-                    Ok(dict.to_object(py))
-                })?;
-                Ok(Some(py_dict))
-            } else {
-                Err(PyErr::new::<pyo3::exceptions::PyStopAsyncIteration, _>(
-                    "Iterator exhausted",
-                ))
+    /// Return the runtime version the client is currently using.
+    ///
+    /// Returns:
+    ///     dict: The `spec_version` and `transaction_version` of the runtime.
+    fn runtime_version(&self, py: Python) -> PyResult<PyObject> {
+        let runtime_version = self.api.runtime_version();
+        let dict = PyDict::new(py);
+        dict.set_item("spec_version", runtime_version.spec_version)?;
+        dict.set_item("transaction_version", runtime_version.transaction_version)?;
+        Ok(dict.to_object(py))
+    }
+
+    /// Submit a batch of calls with `pallet_utility` and wait for it to be finalized,
+    /// asynchronously.
+    ///
+    /// Args:
+    ///     from (Keypair | object): The keypair or external signer to sign the transaction.
+    ///     calls (list): The Calls to batch.
+    ///     mode (str, optional): How failures are handled. "all" (the default) uses
+    ///         `Utility.batch_all`, which reverts every call if one fails. "interrupt" uses
+    ///         `Utility.batch`, which stops at the first failure. "force" uses
+    ///         `Utility.force_batch`, which carries on past failures.
+    ///     **params: Optional transaction parameters, as for `sign_and_submit`.
+    ///
+    /// Returns:
+    ///     dict: The `block_hash`, `extrinsic_hash` and `events`, as for
+    ///         `TransactionProgress.wait_for_finalized_success`, and the calls that `failed`, each
+    ///         a dictionary with the call's `index` in `calls` and its dispatch `error`, from the
+    ///         `BatchInterrupted` and `ItemFailed` events. Calls within nested batches aren't
+    ///         listed, only the top-level call containing them if it failed.
+    ///
+    /// Raises:
+    ///     DispatchError: If the batch fails on-chain, including any failed call in "all" mode.
+    ///     RuntimeError: If signing or submitting the transaction fails.
+    ///     ValueError: If the mode is unknown.
+    ///     TypeError: If `calls` contains something other than Calls.
+    #[pyo3(signature = (from, calls, mode="all", **params))]
+    fn batch<'py>(
+        &self,
+        py: Python<'py>,
+        from: &PyAny,
+        calls: &PyList,
+        mode: &str,
+        params: Option<&PyDict>,
+    ) -> PyResult<&'py PyAny> {
+        let api = self.api.clone();
+        let nonces = self.nonces.clone();
+        let entry_name = match mode {
+            "all" => "batch_all",
+            "interrupt" => "batch",
+            "force" => "force_batch",
+            _ => {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Unknown batch mode: {}",
+                    mode
+                )))
             }
-        });
-        Ok(Some(future?.into()))
+        };
+        let calls = calls
+            .iter()
+            .map(|call| call.extract::<PyRef<Call>>()?.value(py, self.account_id20))
+            .collect::<PyResult<Vec<Value>>>()?;
+        let call_batches: Vec<Vec<NestedBatch>> = calls.iter().map(nested_batches).collect();
+        let options = TxOptions::from_kwargs(params, self.account_id20)?;
+        let signer = TxSigner::extract(from)?;
+        future_into_py(py, async move {
+            let tx_payload = tx("Utility", entry_name, vec![Value::unnamed_composite(calls)]);
+            let progress = submit_and_watch(&api, &nonces, &tx_payload, &signer, &options).await?;
+            let events = wait_for_success(progress, &nonces, Some(&signer.account_id())).await?;
+
+            let metadata = api.metadata();
+            Python::with_gil(|py| -> PyResult<PyObject> {
+                let dict = extrinsic_events_to_py_dict(py, &events)?;
+                let failed = batch_failures(py, &metadata, &events, &call_batches)?;
+                dict.set_item("failed", failed)?;
+                Ok(dict.to_object(py))
+            })
+        })
     }
-}
 
-/// A client for interacting with a Substrate-based blockchain.
-///
-/// This class provides methods to interact with the blockchain, including fetching storage entries,
-/// constants, events, making runtime API calls, and submitting transactions.
-#[pyclass]
-struct SubxtClient {
-    api: Arc<OnlineClient<PolkadotConfig>>,
-}
+    /// Dispatch a call with root origin via `Sudo.sudo`, and wait for it to be finalized,
+    /// asynchronously.
+    ///
+    /// Args:
+    ///     from (Keypair | object): The keypair or external signer to sign the transaction.
+    ///     call (Call): The call to dispatch.
+    ///     **params: Optional transaction parameters, as for `sign_and_submit`.
+    ///
+    /// Returns:
+    ///     dict: The `block_hash`, `extrinsic_hash` and `events`, as for
+    ///         `TransactionProgress.wait_for_finalized_success`.
+    ///
+    /// Raises:
+    ///     DispatchError: If the transaction or the wrapped call fails on-chain.
+    ///     RuntimeError: If signing or submitting the transaction fails.
+    #[pyo3(signature = (from, call, **params))]
+    fn sudo<'py>(
+        &self,
+        py: Python<'py>,
+        from: &PyAny,
+        call: &Call,
+        params: Option<&PyDict>,
+    ) -> PyResult<&'py PyAny> {
+        let api = self.api.clone();
+        let nonces = self.nonces.clone();
+        let call = call.value(py, self.account_id20)?;
+        let options = TxOptions::from_kwargs(params, self.account_id20)?;
+        let signer = TxSigner::extract(from)?;
+        future_into_py(py, async move {
+            let tx_payload = tx("Sudo", "sudo", vec![call]);
+            let events = submit_wrapped(
+                &api,
+                &nonces,
+                &tx_payload,
+                &signer,
+                &options,
+                Some(("Sudo", "Sudid")),
+            )
+            .await?;
+            Python::with_gil(|py| Ok(extrinsic_events_to_py_dict(py, &events)?.to_object(py)))
+        })
+    }
 
-#[pymethods]
-impl SubxtClient {
-    /// Create a new SubxtClient instance asynchronously.
+    /// Dispatch a call with root origin via `Sudo.sudo_unchecked_weight`, and wait for it to be
+    /// finalized, asynchronously.
+    ///
+    /// Args:
+    ///     from (Keypair | object): The keypair or external signer to sign the transaction.
+    ///     call (Call): The call to dispatch.
+    ///     weight (tuple): The `(ref_time, proof_size)` weight to charge for the call.
+    ///     **params: Optional transaction parameters, as for `sign_and_submit`.
     ///
     /// Returns:
-    ///     SubxtClient: A new client connected to the default network.
+    ///     dict: The `block_hash`, `extrinsic_hash` and `events`, as for
+    ///         `TransactionProgress.wait_for_finalized_success`.
     ///
     /// Raises:
-    ///     RuntimeError: If the client fails to connect.
-    #[staticmethod]
-    #[pyo3(name = "new")]
-    fn py_new(py: Python<'_>) -> PyResult<&PyAny> {
-        future_into_py(py, async {
-            match OnlineClient::<PolkadotConfig>::new().await {
-                Ok(api) => Ok(SubxtClient { api: Arc::new(api) }),
-                Err(e) => Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
-                    e.to_string(),
-                )),
-            }
+    ///     DispatchError: If the transaction or the wrapped call fails on-chain.
+    ///     RuntimeError: If signing or submitting the transaction fails.
+    #[pyo3(signature = (from, call, weight, **params))]
+    fn sudo_unchecked_weight<'py>(
+        &self,
+        py: Python<'py>,
+        from: &PyAny,
+        call: &Call,
+        weight: (u64, u64),
+        params: Option<&PyDict>,
+    ) -> PyResult<&'py PyAny> {
+        let api = self.api.clone();
+        let nonces = self.nonces.clone();
+        let call = call.value(py, self.account_id20)?;
+        let options = TxOptions::from_kwargs(params, self.account_id20)?;
+        let signer = TxSigner::extract(from)?;
+        future_into_py(py, async move {
+            let tx_payload = tx(
+                "Sudo",
+                "sudo_unchecked_weight",
+                vec![call, weight_value(weight.0, weight.1)],
+            );
+            let events = submit_wrapped(
+                &api,
+                &nonces,
+                &tx_payload,
+                &signer,
+                &options,
+                Some(("Sudo", "Sudid")),
+            )
+            .await?;
+            Python::with_gil(|py| Ok(extrinsic_events_to_py_dict(py, &events)?.to_object(py)))
         })
     }
 
-    /// Create a new SubxtClient instance from a URL asynchronously.
+    /// Dispatch a call on behalf of another account via `Proxy.proxy`, and wait for it to be
+    /// finalized, asynchronously.
     ///
     /// Args:
-    ///     url (str): The URL of the node to connect to.
+    ///     from (Keypair | object): The keypair or external signer to sign the transaction.
+    ///     real (bytes | str): The proxied account, as bytes, a hex string or an SS58 address.
+    ///     call (Call): The call to dispatch.
+    ///     force_proxy_type (str, optional): Only use a proxy of this type, e.g. "Any" or
+    ///         "Staking".
+    ///     **params: Optional transaction parameters, as for `sign_and_submit`.
     ///
     /// Returns:
-    ///     SubxtClient: A new client connected to the specified node.
+    ///     dict: The `block_hash`, `extrinsic_hash` and `events`, as for
+    ///         `TransactionProgress.wait_for_finalized_success`.
     ///
     /// Raises:
-    ///     RuntimeError: If the client fails to connect.
-    #[staticmethod]
-    #[pyo3(name = "from_url")]
-    fn from_url(py: Python<'_>, url: String) -> PyResult<&PyAny> {
-        future_into_py(py, async {
-            match OnlineClient::<PolkadotConfig>::from_url(url).await {
-                Ok(api) => Ok(SubxtClient { api: Arc::new(api) }),
-                Err(e) => Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
-                    e.to_string(),
-                )),
+    ///     DispatchError: If the transaction or the wrapped call fails on-chain.
+    ///     RuntimeError: If signing or submitting the transaction fails.
+    ///     ValueError: If the proxied account id is invalid.
+    #[pyo3(signature = (from, real, call, force_proxy_type=None, **params))]
+    fn proxy<'py>(
+        &self,
+        py: Python<'py>,
+        from: &PyAny,
+        real: &PyAny,
+        call: &Call,
+        force_proxy_type: Option<String>,
+        params: Option<&PyDict>,
+    ) -> PyResult<&'py PyAny> {
+        let api = self.api.clone();
+        let nonces = self.nonces.clone();
+        let real = match py_to_account_id(real)? {
+            ChainAccountId::Id32(account_id) => {
+                account_id_to_value(account_id.0.to_vec(), AddressUse::Extrinsic)
             }
+            ChainAccountId::Id20(account_id) => Value::from_bytes(account_id),
+        };
+        let force_proxy_type =
+            option_value(force_proxy_type.map(|name| Value::unnamed_variant(name, vec![])));
+        let call = call.value(py, self.account_id20)?;
+        let options = TxOptions::from_kwargs(params, self.account_id20)?;
+        let signer = TxSigner::extract(from)?;
+        future_into_py(py, async move {
+            let tx_payload = tx("Proxy", "proxy", vec![real, force_proxy_type, call]);
+            let events = submit_wrapped(
+                &api,
+                &nonces,
+                &tx_payload,
+                &signer,
+                &options,
+                Some(("Proxy", "ProxyExecuted")),
+            )
+            .await?;
+            Python::with_gil(|py| Ok(extrinsic_events_to_py_dict(py, &events)?.to_object(py)))
         })
     }
 
-    /// Fetch a storage entry from the blockchain asynchronously.
+    /// Dispatch a call from a derivative account via `Utility.as_derivative`, and wait for it to
+    /// be finalized, asynchronously.
+    ///
+    /// Unlike `Sudo.sudo` and `Proxy.proxy`, `Utility.as_derivative` emits no event with the
+    /// wrapped call's result: a failed call fails the whole extrinsic instead.
+    ///
+    /// Args:
+    ///     from (Keypair | object): The keypair or external signer to sign the transaction.
+    ///     index (int): The index of the derivative account.
+    ///     call (Call): The call to dispatch.
+    ///     **params: Optional transaction parameters, as for `sign_and_submit`.
+    ///
+    /// Returns:
+    ///     dict: The `block_hash`, `extrinsic_hash` and `events`, as for
+    ///         `TransactionProgress.wait_for_finalized_success`.
+    ///
+    /// Raises:
+    ///     DispatchError: If the transaction fails on-chain, including when the wrapped call fails.
+    ///     RuntimeError: If signing or submitting the transaction fails.
+    #[pyo3(signature = (from, index, call, **params))]
+    fn as_derivative<'py>(
+        &self,
+        py: Python<'py>,
+        from: &PyAny,
+        index: u16,
+        call: &Call,
+        params: Option<&PyDict>,
+    ) -> PyResult<&'py PyAny> {
+        let api = self.api.clone();
+        let nonces = self.nonces.clone();
+        let call = call.value(py, self.account_id20)?;
+        let options = TxOptions::from_kwargs(params, self.account_id20)?;
+        let signer = TxSigner::extract(from)?;
+        future_into_py(py, async move {
+            let tx_payload = tx(
+                "Utility",
+                "as_derivative",
+                vec![Value::u128(index as u128), call],
+            );
+            let events =
+                submit_wrapped(&api, &nonces, &tx_payload, &signer, &options, None).await?;
+            Python::with_gil(|py| Ok(extrinsic_events_to_py_dict(py, &events)?.to_object(py)))
+        })
+    }
+
+    /// Estimate the fee of a transaction asynchronously.
+    ///
+    /// The transaction is signed but not submitted, and the fee is queried with
+    /// `TransactionPaymentApi.query_info` and `query_fee_details`.
     ///
     /// Args:
+    ///     from (Keypair | object): The keypair or external signer that would sign the transaction.
     ///     pallet_name (str): The name of the pallet.
-    ///     entry_name (str): The name of the storage entry.
-    ///     key (list): A list of keys for the storage entry.
+    ///     entry_name (str): The name of the extrinsic.
+    ///     payload (list): A list of arguments for the extrinsic.
+    ///     **params: Optional transaction parameters, as for `sign_and_submit`.
     ///
     /// Returns:
-    ///     Any: The value of the storage entry.
+    ///     dict: The `partial_fee`, `weight` and dispatch `class` of the transaction, and the
+    ///         `fee_details` with its `inclusion_fee` breakdown and `tip`.
     ///
     /// Raises:
-    ///     RuntimeError: If fetching the storage entry fails.
-    ///     ValueError: If the storage entry is not found.
-    fn storage<'py>(
+    ///     RuntimeError: If signing the transaction or the runtime API call fails.
+    #[pyo3(signature = (from, pallet_name, entry_name, payload, **params))]
+    fn estimate_fee<'py>(
         &self,
         py: Python<'py>,
+        from: &PyAny,
         pallet_name: String,
         entry_name: String,
-        key: &PyList,
+        payload: &PyList,
+        params: Option<&PyDict>,
     ) -> PyResult<&'py PyAny> {
         let api = self.api.clone();
-        let values: Vec<Value> = key
+        let values: Vec<Value> = payload
             .iter()
-            .map(|item| py_object_to_value(item, AddressUse::Storage))
+            .map(|item| py_object_to_value(item, AddressUse::Extrinsic, self.account_id20))
             .collect::<PyResult<Vec<Value>>>()?;
+        let options = TxOptions::from_kwargs(params, self.account_id20)?;
+        let signer = TxSigner::extract(from)?;
         future_into_py(py, async move {
-            let storage_query = subxt::dynamic::storage(pallet_name, entry_name, values);
-            let result = api
-                .storage()
-                .at_latest()
-                .await
-                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?
-                .fetch(&storage_query)
-                .await
-                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+            let tx_payload = tx(pallet_name, entry_name, values);
+            let extrinsic = create_signed_for_query(&api, &tx_payload, &signer, &options).await?;
+            let mut args = extrinsic.encoded().to_vec();
+            (extrinsic.encoded().len() as u32).encode_to(&mut args);
 
-            match result {
-                Some(value) => {
-                    let decoded = value.to_value().map_err(|e| {
-                        PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string())
-                    })?;
-                    let py_value = Python::with_gil(|py| decoded_value_to_py_object(py, &decoded))?;
-                    Ok(py_value)
-                }
-                None => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
-                    "Storage not found",
-                )),
-            }
+            let info =
+                raw_runtime_api_call(&api, "TransactionPaymentApi", "query_info", args.clone())
+                    .await?;
+            let fee_details =
+                raw_runtime_api_call(&api, "TransactionPaymentApi", "query_fee_details", args)
+                    .await?;
+
+            Python::with_gil(|py| -> PyResult<PyObject> {
+                let py_info = decoded_value_to_py_object(py, &info)?;
+                let dict = py_info.as_ref(py).downcast::<PyDict>()?;
+                dict.set_item("fee_details", decoded_value_to_py_object(py, &fee_details)?)?;
+                Ok(py_info)
+            })
         })
     }
 
-    /// Fetch a constant value from the blockchain asynchronously.
+    /// Check whether a transaction would succeed, without submitting it, asynchronously.
+    ///
+    /// With the "dry_run_api" method, the signed transaction is checked with
+    /// `TaggedTransactionQueue.validate_transaction`, then its call is dispatched from the
+    /// signer's origin with `DryRunApi.dry_run_call`. With the "system_dry_run" method, the signed
+    /// transaction is applied with the `system_dryRun` RPC method. Either way this happens on top
+    /// of the latest finalized block, and the state changes are discarded.
     ///
     /// Args:
+    ///     from (Keypair | object): The keypair or external signer that would sign the transaction.
     ///     pallet_name (str): The name of the pallet.
-    ///     constant_name (str): The name of the constant.
+    ///     entry_name (str): The name of the extrinsic.
+    ///     payload (list): A list of arguments for the extrinsic.
+    ///     method (str, optional): "dry_run_api" or "system_dry_run". Defaults to "dry_run_api"
+    ///         if the runtime has `DryRunApi`, else "system_dry_run".
+    ///     **params: Optional transaction parameters, as for `sign_and_submit`.
     ///
     /// Returns:
-    ///     Any: The value of the constant.
+    ///     dict: `success` is True if the transaction would be applied and dispatched
+    ///         successfully. Otherwise `validity_error` is set if the transaction is invalid
+    ///         (e.g. can't pay fees or has a bad nonce), or `dispatch_error` if the call itself
+    ///         would fail; module errors are resolved to their `pallet`, `error` and `docs`.
     ///
     /// Raises:
-    ///     RuntimeError: If fetching the constant fails.
-    fn constant<'py>(
+    ///     RuntimeError: If signing the transaction or the dry run fails, or the runtime has no
+    ///         `DryRunApi` for the "dry_run_api" method.
+    ///     ValueError: If the method is unknown.
+    #[pyo3(signature = (from, pallet_name, entry_name, payload, *, method=None, **params))]
+    fn dry_run<'py>(
         &self,
         py: Python<'py>,
+        from: &PyAny,
         pallet_name: String,
-        constant_name: String,
+        entry_name: String,
+        payload: &PyList,
+        method: Option<&str>,
+        params: Option<&PyDict>,
     ) -> PyResult<&'py PyAny> {
         let api = self.api.clone();
+        let rpc = self.rpc.clone();
+        // Whether to use `DryRunApi`, or None to use it if the runtime has it
+        let use_dry_run_api = match method {
+            None => None,
+            Some("dry_run_api") => Some(true),
+            Some("system_dry_run") => Some(false),
+            Some(method) => {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Unknown dry run method: {}",
+                    method
+                )))
+            }
+        };
+        let values: Vec<Value> = payload
+            .iter()
+            .map(|item| py_object_to_value(item, AddressUse::Extrinsic, self.account_id20))
+            .collect::<PyResult<Vec<Value>>>()?;
+        let options = TxOptions::from_kwargs(params, self.account_id20)?;
+        let signer = TxSigner::extract(from)?;
         future_into_py(py, async move {
-            let constant_query = subxt::dynamic::constant(pallet_name, constant_name);
-
-            let value = api
-                .constants()
-                .at(&constant_query)
+            let tx_payload = tx(pallet_name, entry_name, values);
+            let call_data = api
+                .tx()
+                .call_data(&tx_payload)
                 .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+            let extrinsic = create_signed_for_query(&api, &tx_payload, &signer, &options).await?;
+            // A mortal transaction is checkpointed at this block or an earlier one
+            let at = api
+                .blocks()
+                .at_latest()
+                .await
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?
+                .hash();
+            let has_dry_run_api = api
+                .metadata()
+                .runtime_api_trait_by_name("DryRunApi")
+                .and_then(|runtime_api| runtime_api.method_by_name("dry_run_call"))
+                .is_some();
 
-            let decoded = value
-                .to_value()
-                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
-            let py_value = Python::with_gil(|py| decoded_value_to_py_object(py, &decoded))?;
-            Ok(py_value)
+            let outcome = if use_dry_run_api.unwrap_or(has_dry_run_api) {
+                // The dry run only dispatches the call, so check the transaction's validity first
+                match validate_transaction(&api, extrinsic.encoded(), at).await? {
+                    Some(error) => DryRunOutcome::ValidityError(error),
+                    None => dry_run_call(&api, call_data, &signer.account_id())
+                        .await?
+                        .ok_or_else(|| {
+                            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+                                "The runtime has no DryRunApi.dry_run_call API",
+                            )
+                        })?,
+                }
+            } else {
+                system_dry_run(&api, &rpc, extrinsic.encoded(), at).await?
+            };
+
+            let metadata = api.metadata();
+            Python::with_gil(|py| -> PyResult<PyObject> {
+                let dict = PyDict::new(py);
+                dict.set_item("success", matches!(outcome, DryRunOutcome::Success))?;
+                match &outcome {
+                    DryRunOutcome::Success => {}
+                    DryRunOutcome::DispatchError(error) => dict.set_item(
+                        "dispatch_error",
+                        dispatch_error_value_to_py_object(py, &metadata, error)?,
+                    )?,
+                    DryRunOutcome::ValidityError(error) => {
+                        dict.set_item("validity_error", decoded_value_to_py_object(py, error)?)?
+                    }
+                }
+                Ok(dict.to_object(py))
+            })
         })
     }
 
-    /// Fetch events from the blockchain asynchronously.
+    /// Return the hash of a call, as used to identify multisig operations.
+    ///
+    /// Args:
+    ///     pallet_name (str): The name of the pallet.
+    ///     entry_name (str): The name of the extrinsic.
+    ///     payload (list): A list of arguments for the extrinsic.
     ///
     /// Returns:
-    ///     list: A list of events, where each event is a dictionary containing pallet, variant, and fields.
+    ///     str: The blake2-256 hash of the SCALE-encoded call as a hexadecimal string.
     ///
     /// Raises:
-    ///     RuntimeError: If fetching events fails.
-    fn events<'py>(&self, py: Python<'py>) -> PyResult<&'py PyAny> {
-        let api = self.api.clone();
-        future_into_py(py, async move {
-            let events =
-                api.events().at_latest().await.map_err(|e| {
-                    PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string())
-                })?;
-
-            let events_vec: Vec<_> = events
-                .iter()
-                .collect::<Result<Vec<_>, _>>()
-                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+    ///     RuntimeError: If the call cannot be encoded against the runtime metadata.
+    fn call_hash(&self, pallet_name: &str, entry_name: &str, payload: &PyList) -> PyResult<String> {
+        let values: Vec<Value> = payload
+            .iter()
+            .map(|item| py_object_to_value(item, AddressUse::Extrinsic, self.account_id20))
+            .collect::<PyResult<Vec<Value>>>()?;
+        let call_data = encode_call_data(&self.api, pallet_name, entry_name, values)?;
+        Ok(hex::encode(blake2_256(&call_data)))
+    }
 
-            let py_events: PyResult<PyObject> = Python::with_gil(|py| {
-                let py_list = PyList::new(
-                    py,
-                    events_vec.iter().map(|event| {
-                        let py_event = PyDict::new(py);
-                        py_event.set_item("pallet", event.pallet_name()).unwrap();
-                        py_event.set_item("variant", event.variant_name()).unwrap();
-                        py_event
-                            .set_item(
-                                "fields",
-                                composite_to_py_object(py, &event.field_values().unwrap()).unwrap(),
-                            )
-                            .unwrap();
-                        py_event.to_object(py)
-                    }),
-                );
-                Ok(py_list.into())
-            });
-            py_events
+    /// Fetch the timepoint of a pending multisig operation asynchronously.
+    ///
+    /// Args:
+    ///     multisig_account (str): The multisig account id, as returned by `multisig_account_id`.
+    ///     call_hash (str): The hash of the call, as returned by `call_hash`.
+    ///
+    /// Returns:
+    ///     dict | None: The `height` and `index` of the extrinsic that opened the operation, or
+    ///         None if there is no pending operation for this call.
+    ///
+    /// Raises:
+    ///     RuntimeError: If fetching the storage fails.
+    fn multisig_timepoint<'py>(
+        &self,
+        py: Python<'py>,
+        multisig_account: &PyAny,
+        call_hash: &str,
+    ) -> PyResult<&'py PyAny> {
+        let api = self.api.clone();
+        let multisig_account = py_to_account_id(multisig_account)?;
+        let call_hash = parse_call_hash(call_hash)?;
+        future_into_py(py, async move {
+            let timepoint = fetch_multisig_timepoint(&api, &multisig_account, call_hash).await?;
+            Python::with_gil(|py| -> PyResult<PyObject> {
+                match timepoint {
+                    Some((height, index)) => {
+                        let dict = PyDict::new(py);
+                        dict.set_item("height", height)?;
+                        dict.set_item("index", index)?;
+                        Ok(dict.to_object(py))
+                    }
+                    None => Ok(py.None()),
+                }
+            })
         })
     }
 
-    /// Perform a runtime API call to the blockchain asynchronously.
+    /// Approve a multisig operation without executing it, asynchronously.
+    ///
+    /// Submits `Multisig.approve_as_multi` for the call. The first approval opens the operation;
+    /// later approvals use the timepoint from the pending `Multisig.Multisigs` entry.
     ///
     /// Args:
-    ///     pallet_name (str): The name of the pallet.
-    ///     entry_name (str): The name of the runtime API function.
-    ///     key (list): A list of arguments for the runtime API call.
+    ///     from (Keypair | object): One of the signatories, or an external signer for one.
+    ///     signatories (list): All signatories of the multisig account, including `from`.
+    ///     threshold (int): The number of approvals needed to execute the call.
+    ///     pallet_name (str): The name of the pallet of the call.
+    ///     entry_name (str): The name of the extrinsic.
+    ///     payload (list): A list of arguments for the extrinsic.
+    ///     **params: Optional transaction parameters, as for `sign_and_submit`.
     ///
     /// Returns:
-    ///     Any: The result of the runtime API call.
+    ///     TransactionProgress: The transaction's status updates, with its hash as `tx_hash`.
+    ///         Wait for it before reading the updated `Multisig.Multisigs` entry.
     ///
     /// Raises:
-    ///     RuntimeError: If the runtime API call fails.
-    fn runtime_api_call<'py>(
+    ///     RuntimeError: If signing or submitting the transaction fails.
+    ///     ValueError: If `from` is not one of the signatories.
+    #[pyo3(signature = (from, signatories, threshold, pallet_name, entry_name, payload, **params))]
+    #[allow(clippy::too_many_arguments)]
+    fn multisig_approve<'py>(
         &self,
         py: Python<'py>,
-        pallet_name: String,
-        entry_name: String,
-        key: &PyList,
+        from: &PyAny,
+        signatories: &PyList,
+        threshold: u16,
+        pallet_name: &str,
+        entry_name: &str,
+        payload: &PyList,
+        params: Option<&PyDict>,
     ) -> PyResult<&'py PyAny> {
         let api = self.api.clone();
-        let values: Vec<Value> = key
+        let nonces = self.nonces.clone();
+        let options = TxOptions::from_kwargs(params, self.account_id20)?;
+        let signer = TxSigner::extract(from)?;
+        let signatories = py_to_signatories(signatories)?;
+        let other_signatories = other_signatories(&signatories, &signer.account_id())?;
+        let multisig_account = multi_account_id(&signatories, threshold);
+        let values: Vec<Value> = payload
             .iter()
-            .map(|item| py_object_to_value(item, AddressUse::Storage))
+            .map(|item| py_object_to_value(item, AddressUse::Extrinsic, self.account_id20))
             .collect::<PyResult<Vec<Value>>>()?;
+        let call_hash = blake2_256(&encode_call_data(&api, pallet_name, entry_name, values)?);
         future_into_py(py, async move {
-            let runtime_api_call =
-                subxt::dynamic::runtime_api_call(&pallet_name, &entry_name, values);
-
-            let result = api
-                .runtime_api()
-                .at_latest()
-                .await
-                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?
-                .call(runtime_api_call)
-                .await
-                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
-
-            let decoded = result
-                .to_value()
-                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
-            let py_value = Python::with_gil(|py| decoded_value_to_py_object(py, &decoded))?;
-            Ok(py_value)
+            let timepoint = fetch_multisig_timepoint(&api, &multisig_account, call_hash).await?;
+            let tx_payload = tx(
+                "Multisig",
+                "approve_as_multi",
+                vec![
+                    Value::u128(threshold as u128),
+                    other_signatories,
+                    option_value(timepoint.map(timepoint_value)),
+                    Value::from_bytes(call_hash),
+                    weight_value(0, 0),
+                ],
+            );
+            let progress = submit_and_watch(&api, &nonces, &tx_payload, &signer, &options).await?;
+            Ok(TransactionProgress::new(
+                progress,
+                nonces,
+                Some(signer.account_id()),
+            ))
         })
     }
 
-    /// Iterate over storage entries from the blockchain asynchronously.
+    /// Approve and execute a multisig operation asynchronously.
+    ///
+    /// Submits `Multisig.as_multi` with the full call. If this is the final approval the call is
+    /// dispatched from the multisig account.
     ///
     /// Args:
-    ///     pallet_name (str): The name of the pallet.
-    ///     entry_name (str): The name of the storage entry.
-    ///     key (bytes): The prefix key for iteration.
+    ///     from (Keypair | object): One of the signatories, or an external signer for one.
+    ///     signatories (list): All signatories of the multisig account, including `from`.
+    ///     threshold (int): The number of approvals needed to execute the call.
+    ///     pallet_name (str): The name of the pallet of the call.
+    ///     entry_name (str): The name of the extrinsic.
+    ///     payload (list): A list of arguments for the extrinsic.
+    ///     max_weight (tuple, optional): The maximum `(ref_time, proof_size)` weight the call may
+    ///         use. By default this is queried with `TransactionPaymentCallApi.query_call_info`.
+    ///     **params: Optional transaction parameters, as for `sign_and_submit`.
     ///
     /// Returns:
-    ///     StorageIterator: An asynchronous iterator over storage key-value pairs.
+    ///     TransactionProgress: The transaction's status updates, with its hash as `tx_hash`.
+    ///         Wait for it before reading the updated `Multisig.Multisigs` entry.
     ///
     /// Raises:
-    ///     RuntimeError: If the iteration fails.
-    fn storage_iter<'py>(
+    ///     RuntimeError: If the weight query, signing or submitting the transaction fails.
+    ///     ValueError: If `from` is not one of the signatories.
+    #[pyo3(signature = (from, signatories, threshold, pallet_name, entry_name, payload, max_weight=None, **params))]
+    #[allow(clippy::too_many_arguments)]
+    fn multisig_execute<'py>(
         &self,
         py: Python<'py>,
-        pallet_name: String,
-        entry_name: String,
-        key: Vec<u8>,
+        from: &PyAny,
+        signatories: &PyList,
+        threshold: u16,
+        pallet_name: &str,
+        entry_name: &str,
+        payload: &PyList,
+        max_weight: Option<(u64, u64)>,
+        params: Option<&PyDict>,
     ) -> PyResult<&'py PyAny> {
         let api = self.api.clone();
+        let nonces = self.nonces.clone();
+        let options = TxOptions::from_kwargs(params, self.account_id20)?;
+        let signer = TxSigner::extract(from)?;
+        let signatories = py_to_signatories(signatories)?;
+        let other_signatories = other_signatories(&signatories, &signer.account_id())?;
+        let multisig_account = multi_account_id(&signatories, threshold);
+        let values: Vec<Value> = payload
+            .iter()
+            .map(|item| py_object_to_value(item, AddressUse::Extrinsic, self.account_id20))
+            .collect::<PyResult<Vec<Value>>>()?;
+        let call_data = encode_call_data(&api, pallet_name, entry_name, values.clone())?;
+        let call = call_value(pallet_name, entry_name, values);
         future_into_py(py, async move {
-            let storage_query =
-                subxt::dynamic::storage(pallet_name, entry_name, vec![Value::from_bytes(key)]);
-
-            let results = api
-                .storage()
-                .at_latest()
-                .await
-                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?
-                .iter(storage_query)
-                .await
-                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
-
-            Ok(StorageIterator {
-                results: Arc::new(tokio::sync::Mutex::new(results)),
-            })
+            let call_hash = blake2_256(&call_data);
+            let timepoint = fetch_multisig_timepoint(&api, &multisig_account, call_hash).await?;
+            let (ref_time, proof_size) = match max_weight {
+                Some(max_weight) => max_weight,
+                None => query_call_weight(&api, call.clone(), call_data.len()).await?,
+            };
+            let tx_payload = tx(
+                "Multisig",
+                "as_multi",
+                vec![
+                    Value::u128(threshold as u128),
+                    other_signatories,
+                    option_value(timepoint.map(timepoint_value)),
+                    call,
+                    weight_value(ref_time, proof_size),
+                ],
+            );
+            let progress = submit_and_watch(&api, &nonces, &tx_payload, &signer, &options).await?;
+            Ok(TransactionProgress::new(
+                progress,
+                nonces,
+                Some(signer.account_id()),
+            ))
         })
     }
 
-    /// Sign and submit a transaction to the blockchain asynchronously.
+    /// Cancel a pending multisig operation asynchronously.
+    ///
+    /// Submits `Multisig.cancel_as_multi`. Only the signatory who opened the operation can
+    /// cancel it.
     ///
     /// Args:
-    ///     from (Keypair): The keypair to sign the transaction.
-    ///     pallet_name (str): The name of the pallet.
-    ///     entry_name (str): The name of the extrinsic.
-    ///     payload (list): A list of arguments for the extrinsic.
+    ///     from (Keypair | object): The signatory who opened the operation.
+    ///     signatories (list): All signatories of the multisig account, including `from`.
+    ///     threshold (int): The number of approvals needed to execute the call.
+    ///     call_hash (str): The hash of the call, as returned by `call_hash`.
+    ///     **params: Optional transaction parameters, as for `sign_and_submit`.
     ///
     /// Returns:
-    ///     str: The transaction hash as a hexadecimal string.
+    ///     TransactionProgress: The transaction's status updates, with its hash as `tx_hash`.
+    ///         Wait for it before reading the updated `Multisig.Multisigs` entry.
     ///
     /// Raises:
     ///     RuntimeError: If signing or submitting the transaction fails.
-    fn sign_and_submit<'py>(
+    ///     ValueError: If `from` is not one of the signatories or there is no pending operation.
+    #[pyo3(signature = (from, signatories, threshold, call_hash, **params))]
+    fn multisig_cancel<'py>(
         &self,
         py: Python<'py>,
-        from: Keypair,
-        pallet_name: String,
-        entry_name: String,
-        payload: &PyList,
+        from: &PyAny,
+        signatories: &PyList,
+        threshold: u16,
+        call_hash: &str,
+        params: Option<&PyDict>,
     ) -> PyResult<&'py PyAny> {
         let api = self.api.clone();
-        let values: Vec<Value> = payload
-            .iter()
-            .map(|item| py_object_to_value(item, AddressUse::Extrinsic))
-            .collect::<PyResult<Vec<Value>>>()?;
+        let nonces = self.nonces.clone();
+        let options = TxOptions::from_kwargs(params, self.account_id20)?;
+        let signer = TxSigner::extract(from)?;
+        let signatories = py_to_signatories(signatories)?;
+        let other_signatories = other_signatories(&signatories, &signer.account_id())?;
+        let multisig_account = multi_account_id(&signatories, threshold);
+        let call_hash = parse_call_hash(call_hash)?;
         future_into_py(py, async move {
-            let tx_params = Params::new().build();
-            let tx_payload = tx(pallet_name, entry_name, values);
-            let hash = api
-                .tx()
-                .sign_and_submit(&tx_payload, &from, tx_params)
-                .await
-                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
-            let hex_string = format!("{:?}", hash);
-            Ok(hex_string)
+            let timepoint = fetch_multisig_timepoint(&api, &multisig_account, call_hash)
+                .await?
+                .ok_or_else(|| {
+                    PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                        "No pending multisig operation for this call",
+                    )
+                })?;
+            let tx_payload = tx(
+                "Multisig",
+                "cancel_as_multi",
+                vec![
+                    Value::u128(threshold as u128),
+                    other_signatories,
+                    timepoint_value(timepoint),
+                    Value::from_bytes(call_hash),
+                ],
+            );
+            let progress = submit_and_watch(&api, &nonces, &tx_payload, &signer, &options).await?;
+            Ok(TransactionProgress::new(
+                progress,
+                nonces,
+                Some(signer.account_id()),
+            ))
         })
     }
 
@@ -606,21 +4719,567 @@ impl SubxtClient {
     }
 }
 
+/// A client for signing transactions without a node connection.
+///
+/// The client is built from the chain's metadata, genesis hash and runtime version, which can be
+/// obtained on a connected machine with `SubxtClient.metadata`, `SubxtClient.genesis_hash` and
+/// `SubxtClient.runtime_version`. Signed extrinsics can then be broadcast with
+/// `SubxtClient.submit_raw`.
+///
+/// .. code-block:: python
+///
+///     client = OfflineClient.from_metadata_file("metadata.scale", genesis_hash, 1, 1)
+///     extrinsic = client.create_signed(alice, "Balances", "transfer_allow_death", payload, nonce=0)
+#[pyclass(name = "OfflineClient")]
+struct PyOfflineClient {
+    api: OfflineClient<ChainConfig>,
+    /// Whether the chain uses Ethereum-style 20-byte account ids.
+    account_id20: bool,
+}
+
+#[pymethods]
+impl PyOfflineClient {
+    /// Create a new OfflineClient.
+    ///
+    /// Args:
+    ///     metadata (bytes): The SCALE-encoded runtime metadata.
+    ///     genesis_hash (str): The chain's genesis hash as a hexadecimal string.
+    ///     spec_version (int): The runtime's spec version.
+    ///     transaction_version (int): The runtime's transaction version.
+    ///
+    /// Returns:
+    ///     OfflineClient: A new offline client.
+    ///
+    /// Raises:
+    ///     ValueError: If the metadata or genesis hash is invalid.
+    #[new]
+    fn new(
+        metadata: &[u8],
+        genesis_hash: &str,
+        spec_version: u32,
+        transaction_version: u32,
+    ) -> PyResult<Self> {
+        let metadata = subxt::Metadata::decode(&mut &metadata[..]).map_err(|e| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid metadata: {}", e))
+        })?;
+        let runtime_version = RuntimeVersion {
+            spec_version,
+            transaction_version,
+        };
+        Ok(PyOfflineClient {
+            account_id20: uses_account_id20(&metadata),
+            api: OfflineClient::new(parse_hash(genesis_hash)?, runtime_version, metadata),
+        })
+    }
+
+    /// Create a new OfflineClient from a saved metadata file.
+    ///
+    /// Args:
+    ///     path (str): The path of a file containing the SCALE-encoded runtime metadata.
+    ///     genesis_hash (str): The chain's genesis hash as a hexadecimal string.
+    ///     spec_version (int): The runtime's spec version.
+    ///     transaction_version (int): The runtime's transaction version.
+    ///
+    /// Returns:
+    ///     OfflineClient: A new offline client.
+    ///
+    /// Raises:
+    ///     OSError: If the file can't be read.
+    ///     ValueError: If the metadata or genesis hash is invalid.
+    #[staticmethod]
+    fn from_metadata_file(
+        path: &str,
+        genesis_hash: &str,
+        spec_version: u32,
+        transaction_version: u32,
+    ) -> PyResult<Self> {
+        let metadata = std::fs::read(path)?;
+        Self::new(&metadata, genesis_hash, spec_version, transaction_version)
+    }
+
+    /// Return the genesis hash of the chain.
+    ///
+    /// Returns:
+    ///     str: The genesis hash as a hexadecimal string.
+    fn genesis_hash(&self) -> String {
+        format!("{:?}", self.api.genesis_hash())
+    }
+
+    /// Return the runtime version of the chain.
+    ///
+    /// Returns:
+    ///     dict: The `spec_version` and `transaction_version` of the runtime.
+    fn runtime_version(&self, py: Python) -> PyResult<PyObject> {
+        let runtime_version = self.api.runtime_version();
+        let dict = PyDict::new(py);
+        dict.set_item("spec_version", runtime_version.spec_version)?;
+        dict.set_item("transaction_version", runtime_version.transaction_version)?;
+        Ok(dict.to_object(py))
+    }
+
+    /// Sign a transaction.
+    ///
+    /// Without a node connection the nonce must be given, and the transaction is immortal.
+    ///
+    /// Args:
+    ///     from (Keypair | object): The keypair or external signer to sign the transaction.
+    ///     pallet_name (str): The name of the pallet.
+    ///     entry_name (str): The name of the extrinsic.
+    ///     payload (list): A list of arguments for the extrinsic.
+    ///     **params: Transaction parameters:
+    ///         nonce (int): The account's nonce. Required.
+    ///         tip (int): A tip for the block author, to prioritize the transaction.
+    ///         tip_asset_id (int): Pay the tip (and fee) in this asset, on chains with
+    ///             `ChargeAssetTxPayment`.
+    ///         extras (dict): Values for chain-specific transaction extensions by name, as for
+    ///             `SubxtClient.sign_and_submit`.
+    ///
+    /// Returns:
+    ///     bytes: The SCALE-encoded signed extrinsic.
+    ///
+    /// Raises:
+    ///     RuntimeError: If signing the transaction fails.
+    ///     ValueError: If no nonce is given, `mortality_blocks` is given, or an external signer
+    ///         returns an invalid account id or signature.
+    ///     TypeError: If an unknown transaction parameter is passed.
+    #[pyo3(signature = (from, pallet_name, entry_name, payload, **params))]
+    fn create_signed<'py>(
+        &self,
+        py: Python<'py>,
+        from: &PyAny,
+        pallet_name: &str,
+        entry_name: &str,
+        payload: &PyList,
+        params: Option<&PyDict>,
+    ) -> PyResult<&'py PyBytes> {
+        let values: Vec<Value> = payload
+            .iter()
+            .map(|item| py_object_to_value(item, AddressUse::Extrinsic, self.account_id20))
+            .collect::<PyResult<Vec<Value>>>()?;
+        let tx_params = TxOptions::from_kwargs(params, self.account_id20)?.build_offline()?;
+        let signer = TxSigner::extract(from)?;
+        let tx_payload = tx(pallet_name, entry_name, values);
+        let extrinsic = self
+            .api
+            .tx()
+            .create_signed_offline(&tx_payload, &signer, tx_params)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+        signer.check_error()?;
+        Ok(PyBytes::new(py, extrinsic.encoded()))
+    }
+}
+
+/// Find the calls of a batch that failed, from its `BatchInterrupted` and `ItemFailed` events.
+///
+/// `calls` has the batches nested in each call, e.g. in a call to `Utility.batch_all` or
+/// `Sudo.sudo(Utility.batch(...))`. The events are walked back from the batch's own completion
+/// event, which comes last: each dispatched call ends with its own `ItemCompleted` or `ItemFailed`
+/// event, preceded by the events of the batches nested in it, which are skipped using their known
+/// structure.
+fn batch_failures<'py>(
+    py: Python<'py>,
+    metadata: &subxt::Metadata,
+    events: &ExtrinsicEvents<ChainConfig>,
+    calls: &[Vec<NestedBatch>],
+) -> PyResult<Vec<&'py PyDict>> {
+    let mut utility_events = Vec::new();
+    for event in events.iter() {
+        let event =
+            event.map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+        if event.pallet_name() == "Utility"
+            && (is_batch_end(event.variant_name())
+                || matches!(event.variant_name(), "ItemCompleted" | "ItemFailed"))
+        {
+            let fields = event
+                .field_values()
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+            utility_events.push((event.variant_name().to_owned(), fields));
+        }
+    }
+    let end = match utility_events
+        .iter()
+        .rposition(|(variant, _)| is_batch_end(variant))
+    {
+        Some(end) => end,
+        None => return Ok(Vec::new()),
+    };
+
+    // An interrupted batch ends with `BatchInterrupted`, which has the failed call's index. The
+    // failed call's events are reverted, and the calls after it aren't dispatched.
+    let (variant, fields) = &utility_events[end];
+    let interrupted = match fields.at("index").and_then(|index| index.as_u128()) {
+        Some(index) if variant == "BatchInterrupted" => Some(index as usize),
+        _ => None,
+    };
+    let dispatched = interrupted.unwrap_or(calls.len()).min(calls.len());
+
+    let mut failed = Vec::new();
+    let mut position = end;
+    for (index, nested) in calls[..dispatched].iter().enumerate().rev() {
+        // Each dispatched call ends with its own `ItemCompleted` or `ItemFailed` event
+        if let Some((item_variant, item_fields)) =
+            position.checked_sub(1).map(|item| &utility_events[item])
+        {
+            if item_variant == "ItemFailed" {
+                failed.push(batch_failure(py, metadata, index, item_fields.at("error"))?);
+            }
+        }
+        position = match batch_call_start(&utility_events, position, nested) {
+            Some(start) => start,
+            None => break,
+        };
+    }
+    failed.reverse();
+
+    if let Some(index) = interrupted {
+        failed.push(batch_failure(py, metadata, index, fields.at("error"))?);
+    }
+    Ok(failed)
+}
+
+/// Whether a `Utility` event ends a batch.
+fn is_batch_end(variant: &str) -> bool {
+    matches!(
+        variant,
+        "BatchCompleted" | "BatchCompletedWithErrors" | "BatchInterrupted"
+    )
+}
+
+/// Walk back over the events of a batch's call, which end before `end`, returning where they
+/// start.
+///
+/// The call's events end with its `ItemCompleted` or `ItemFailed` event. A completed call's
+/// nested batches come before it, while a failed call's events are reverted.
+fn batch_call_start(
+    events: &[(String, Composite<u32>)],
+    end: usize,
+    nested: &[NestedBatch],
+) -> Option<usize> {
+    let item = end.checked_sub(1)?;
+    match events[item].0.as_str() {
+        "ItemFailed" => Some(item),
+        "ItemCompleted" => nested
+            .iter()
+            .rev()
+            .try_fold(item, |end, batch| nested_batch_start(events, end, batch)),
+        _ => None,
+    }
+}
+
+/// Walk back over the events of a nested batch, which end before `end`, returning where they
+/// start.
+///
+/// A batch that left no completion event, e.g. because it failed and was reverted, is skipped.
+fn nested_batch_start(
+    events: &[(String, Composite<u32>)],
+    end: usize,
+    batch: &NestedBatch,
+) -> Option<usize> {
+    let last = match end.checked_sub(1) {
+        Some(last) if is_batch_end(&events[last].0) => last,
+        _ => return Some(end),
+    };
+    let (variant, fields) = &events[last];
+    let dispatched = match fields.at("index").and_then(|index| index.as_u128()) {
+        Some(index) if variant == "BatchInterrupted" => index as usize,
+        _ => batch.calls.len(),
+    };
+    batch.calls[..dispatched.min(batch.calls.len())]
+        .iter()
+        .rev()
+        .try_fold(last, |end, nested| batch_call_start(events, end, nested))
+}
+
+/// Describe a failed call of a batch as a dictionary with its `index` and `error`.
+fn batch_failure<'py>(
+    py: Python<'py>,
+    metadata: &subxt::Metadata,
+    index: usize,
+    error: Option<&Value<u32>>,
+) -> PyResult<&'py PyDict> {
+    let item = PyDict::new(py);
+    item.set_item("index", index)?;
+    match error {
+        Some(error) => item.set_item(
+            "error",
+            dispatch_error_value_to_py_object(py, metadata, error)?,
+        )?,
+        None => item.set_item("error", py.None())?,
+    }
+    Ok(item)
+}
+
+/// A `Utility` batch nested in a call, with the batches nested in each of its calls.
+struct NestedBatch {
+    calls: Vec<Vec<NestedBatch>>,
+}
+
+/// Find the `Utility` batches in a call, in dispatch order, including the call itself and any
+/// batches wrapped in it, e.g. by `Sudo.sudo`.
+fn nested_batches(call: &Value) -> Vec<NestedBatch> {
+    if let Some(("Utility", Some(inner))) = variant_parts(call) {
+        if let Some(("batch" | "batch_all" | "force_batch", Some(calls))) = variant_parts(inner) {
+            let calls = match &calls.value {
+                ValueDef::Composite(calls) => calls.values().map(nested_batches).collect(),
+                _ => Vec::new(),
+            };
+            return vec![NestedBatch { calls }];
+        }
+    }
+    match &call.value {
+        ValueDef::Composite(composite) => composite.values().flat_map(nested_batches).collect(),
+        ValueDef::Variant(variant) => variant.values.values().flat_map(nested_batches).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Encode a call against the runtime metadata, as it would appear in an extrinsic.
+fn encode_call_data(
+    api: &OnlineClient<ChainConfig>,
+    pallet_name: &str,
+    entry_name: &str,
+    values: Vec<Value>,
+) -> PyResult<Vec<u8>> {
+    tx(pallet_name, entry_name, values)
+        .encode_call_data(&api.metadata())
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
+}
+
+/// Build a `RuntimeCall` value, for passing a call as an argument to another extrinsic.
+fn call_value(pallet_name: &str, entry_name: &str, values: Vec<Value>) -> Value {
+    Value::unnamed_variant(
+        pallet_name,
+        vec![Value::unnamed_variant(entry_name, values)],
+    )
+}
+
+/// Build an `Option` value.
+fn option_value(value: Option<Value>) -> Value {
+    match value {
+        Some(value) => Value::unnamed_variant("Some", vec![value]),
+        None => Value::unnamed_variant("None", vec![]),
+    }
+}
+
+/// Build a `Weight` value.
+fn weight_value(ref_time: u64, proof_size: u64) -> Value {
+    Value::named_composite(vec![
+        ("ref_time", Value::u128(ref_time as u128)),
+        ("proof_size", Value::u128(proof_size as u128)),
+    ])
+}
+
+/// Build a multisig `Timepoint` value.
+fn timepoint_value((height, index): (u32, u32)) -> Value {
+    Value::named_composite(vec![
+        ("height", Value::u128(height as u128)),
+        ("index", Value::u128(index as u128)),
+    ])
+}
+
+/// Parse a hex-encoded 32-byte block or genesis hash.
+fn parse_hash(hash: &str) -> PyResult<H256> {
+    let mut bytes = [0u8; 32];
+    hex::decode_to_slice(hash.strip_prefix("0x").unwrap_or(hash), &mut bytes).map_err(|e| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid hash: {}", e))
+    })?;
+    Ok(H256(bytes))
+}
+
+/// Parse a hex-encoded 32-byte call hash.
+fn parse_call_hash(call_hash: &str) -> PyResult<[u8; 32]> {
+    let mut bytes = [0u8; 32];
+    hex::decode_to_slice(
+        call_hash.strip_prefix("0x").unwrap_or(call_hash),
+        &mut bytes,
+    )
+    .map_err(|e| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid call hash: {}", e))
+    })?;
+    Ok(bytes)
+}
+
+/// Convert a list of Python account ids to multisig signatories, sorted and deduplicated as
+/// `pallet_multisig` expects.
+fn py_to_signatories(signatories: &PyList) -> PyResult<Vec<ChainAccountId>> {
+    let mut signatories = signatories
+        .iter()
+        .map(py_to_account_id)
+        .collect::<PyResult<Vec<_>>>()?;
+    signatories.sort_by_key(|account_id| account_id.encode());
+    signatories.dedup();
+    Ok(signatories)
+}
+
+/// Return the `other_signatories` argument for multisig calls signed by `signer`.
+fn other_signatories(signatories: &[ChainAccountId], signer: &ChainAccountId) -> PyResult<Value> {
+    if !signatories.contains(signer) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "The signer is not one of the signatories",
+        ));
+    }
+    Ok(Value::unnamed_composite(
+        signatories
+            .iter()
+            .filter(|account_id| *account_id != signer)
+            .map(|account_id| Value::from_bytes(account_id.encode())),
+    ))
+}
+
+/// Derive the account id of a multisig, as `pallet_multisig::Pallet::multi_account_id` does.
+///
+/// Chains with 20-byte account ids use the first 20 bytes of the hash.
+fn multi_account_id(signatories: &[ChainAccountId], threshold: u16) -> ChainAccountId {
+    let who: Vec<u8> = signatories
+        .iter()
+        .flat_map(|account_id| account_id.encode())
+        .collect();
+    let mut data = b"modlpy/utilisuba".to_vec();
+    subxt::ext::codec::Compact(signatories.len() as u32).encode_to(&mut data);
+    data.extend_from_slice(&who);
+    threshold.encode_to(&mut data);
+    let entropy = blake2_256(&data);
+
+    if signatories
+        .iter()
+        .any(|account_id| matches!(account_id, ChainAccountId::Id20(_)))
+    {
+        ChainAccountId::Id20(entropy[..20].try_into().expect("20 bytes; qed"))
+    } else {
+        ChainAccountId::Id32(AccountId32(entropy))
+    }
+}
+
+/// Fetch the timepoint of a pending multisig operation from `Multisig.Multisigs`.
+async fn fetch_multisig_timepoint(
+    api: &OnlineClient<ChainConfig>,
+    multisig_account: &ChainAccountId,
+    call_hash: [u8; 32],
+) -> PyResult<Option<(u32, u32)>> {
+    let storage_query = subxt::dynamic::storage(
+        "Multisig",
+        "Multisigs",
+        vec![
+            Value::from_bytes(multisig_account.encode()),
+            Value::from_bytes(call_hash),
+        ],
+    );
+    let result = api
+        .storage()
+        .at_latest()
+        .await
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?
+        .fetch(&storage_query)
+        .await
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+    let multisig = match result {
+        Some(value) => value
+            .to_value()
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?,
+        None => return Ok(None),
+    };
+    let when = multisig.at("when");
+    match (
+        when.at("height").and_then(|v| v.as_u128()),
+        when.at("index").and_then(|v| v.as_u128()),
+    ) {
+        (Some(height), Some(index)) => Ok(Some((height as u32, index as u32))),
+        _ => Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+            "Unexpected Multisig.Multisigs storage layout",
+        )),
+    }
+}
+
+/// Query the weight of a call with `TransactionPaymentCallApi.query_call_info`.
+async fn query_call_weight(
+    api: &OnlineClient<ChainConfig>,
+    call: Value,
+    call_len: usize,
+) -> PyResult<(u64, u64)> {
+    let runtime_api_call = subxt::dynamic::runtime_api_call(
+        "TransactionPaymentCallApi",
+        "query_call_info",
+        vec![call, Value::u128(call_len as u128)],
+    );
+    let info = api
+        .runtime_api()
+        .at_latest()
+        .await
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?
+        .call(runtime_api_call)
+        .await
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?
+        .to_value()
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+    let weight = info.at("weight");
+    match (
+        weight.at("ref_time").and_then(|v| v.as_u128()),
+        weight.at("proof_size").and_then(|v| v.as_u128()),
+    ) {
+        (Some(ref_time), Some(proof_size)) => Ok((ref_time as u64, proof_size as u64)),
+        _ => Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+            "Unexpected query_call_info result",
+        )),
+    }
+}
+
+/// Derive the account id of a multisig from its signatories and threshold.
+///
+/// Args:
+///     signatories (list): The signatories' account ids, as bytes, hex strings or SS58 addresses.
+///         The order does not matter.
+///     threshold (int): The number of approvals needed to execute a call.
+///
+/// Returns:
+///     str: The multisig account id as a hexadecimal string.
+///
+/// Raises:
+///     ValueError: If an account id is invalid.
+#[pyfunction]
+fn multisig_account_id(signatories: &PyList, threshold: u16) -> PyResult<String> {
+    let signatories = py_to_signatories(signatories)?;
+    Ok(hex::encode(
+        multi_account_id(&signatories, threshold).encode(),
+    ))
+}
+
 // Helper functions for converting values to Python objects
 
+/// Convert an event to a Python dictionary containing its pallet, variant, and fields.
+fn event_to_py_object(py: Python, event: &EventDetails<ChainConfig>) -> PyResult<PyObject> {
+    let fields = event
+        .field_values()
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+    let py_event = PyDict::new(py);
+    py_event.set_item("pallet", event.pallet_name())?;
+    py_event.set_item("variant", event.variant_name())?;
+    py_event.set_item("fields", composite_to_py_object(py, &fields)?)?;
+    Ok(py_event.to_object(py))
+}
+
 /// Convert a Python object to a Subxt `Value`.
 ///
 /// Args:
 ///     item (PyAny): The Python object to convert.
 ///     address_use (AddressUse): Indicates whether the value is for storage or extrinsic use.
+///     account_id20 (bool): Whether the chain uses 20-byte account ids, in which case
+///         40-character hex strings are treated as account ids.
 ///
 /// Returns:
 ///     Value: The corresponding Subxt value.
 ///
 /// Raises:
 ///     TypeError: If the Python object type is unsupported.
-fn py_object_to_value(item: &PyAny, address_use: AddressUse) -> PyResult<Value> {
-    if let Ok(bytes) = item.downcast::<PyBytes>() {
+fn py_object_to_value(
+    item: &PyAny,
+    address_use: AddressUse,
+    account_id20: bool,
+) -> PyResult<Value> {
+    if let Ok(call) = item.extract::<PyRef<Call>>() {
+        call.value(item.py(), account_id20)
+    } else if let Ok(bytes) = item.downcast::<PyBytes>() {
         let bytes = bytes.as_bytes();
         Ok(Value::from_bytes(bytes.to_vec()))
     } else if let Ok(int_val) = item.extract::<i128>() {
@@ -631,26 +5290,36 @@ fn py_object_to_value(item: &PyAny, address_use: AddressUse) -> PyResult<Value>
         Ok(Value::bool(bool_val))
     } else if let Ok(string_val) = item.downcast::<PyString>() {
         let s = string_val.to_string();
-        if s.len() == 64 && s.chars().all(|c| c.is_ascii_hexdigit()) {
+        if account_id20 && s.len() == 40 && s.chars().all(|c| c.is_ascii_hexdigit()) {
+            // Ethereum-style AccountId20, which such runtimes also use as their address type
             let bytes = hex::decode(&s).map_err(|e| {
                 PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
                     "Invalid hex string: {}",
                     e
                 ))
             })?;
-            match address_use {
-                AddressUse::Storage => Ok(Value::from_bytes(bytes)),
-                AddressUse::Extrinsic => {
-                    Ok(Value::unnamed_variant("Id", vec![Value::from_bytes(bytes)]))
-                }
-            }
+            Ok(Value::from_bytes(bytes))
+        } else if s.len() == 64 && s.chars().all(|c| c.is_ascii_hexdigit()) {
+            let bytes = hex::decode(&s).map_err(|e| {
+                PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Invalid hex string: {}",
+                    e
+                ))
+            })?;
+            Ok(account_id_to_value(bytes, address_use))
+        } else if let Some(bytes) = ss58_decode(&s)
+            .ok()
+            .and_then(|(bytes, _)| (bytes.len() == 32).then_some(bytes))
+        {
+            // SS58 addresses are treated the same as hex-encoded AccountId32s
+            Ok(account_id_to_value(bytes, address_use))
         } else {
             Ok(Value::from_bytes(string_val.to_string().into_bytes()))
         }
     } else if let Ok(list_val) = item.downcast::<PyList>() {
         let values: PyResult<Vec<Value>> = list_val
             .iter()
-            .map(|item| py_object_to_value(item, address_use.clone()))
+            .map(|item| py_object_to_value(item, address_use.clone(), account_id20))
             .collect();
         Ok(Value::unnamed_composite(values?))
     } else {
@@ -660,6 +5329,62 @@ fn py_object_to_value(item: &PyAny, address_use: AddressUse) -> PyResult<Value>
     }
 }
 
+/// Convert a Python account id to a `ChainAccountId`.
+///
+/// Accepts 32- or 20-byte account ids as bytes or a (optionally 0x-prefixed) hex string, or an
+/// SS58 address.
+fn py_to_account_id(item: &PyAny) -> PyResult<ChainAccountId> {
+    let bytes = if let Ok(bytes) = item.downcast::<PyBytes>() {
+        bytes.as_bytes().to_vec()
+    } else {
+        let s: &str = item.extract()?;
+        match hex::decode(s.strip_prefix("0x").unwrap_or(s)) {
+            Ok(bytes) => bytes,
+            Err(_) => ss58_decode(s)?.0,
+        }
+    };
+    match bytes.len() {
+        32 => Ok(ChainAccountId::Id32(AccountId32(
+            bytes.try_into().expect("32 bytes; qed"),
+        ))),
+        20 => Ok(ChainAccountId::Id20(
+            bytes.try_into().expect("20 bytes; qed"),
+        )),
+        len => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Invalid account id length: {}",
+            len
+        ))),
+    }
+}
+
+/// Convert 32-byte account id bytes to a Subxt `Value`.
+///
+/// Extrinsic arguments are wrapped in the `MultiAddress::Id` variant, storage keys are not.
+fn account_id_to_value(bytes: Vec<u8>, address_use: AddressUse) -> Value {
+    match address_use {
+        AddressUse::Storage => Value::from_bytes(bytes),
+        AddressUse::Extrinsic => Value::unnamed_variant("Id", vec![Value::from_bytes(bytes)]),
+    }
+}
+
+/// Whether a chain uses Ethereum-style 20-byte account ids, judging by the key of `System.Account`.
+fn uses_account_id20(metadata: &subxt::Metadata) -> bool {
+    let key_ty = match metadata
+        .pallet_by_name("System")
+        .and_then(|pallet| pallet.storage())
+        .and_then(|storage| storage.entry_by_name("Account"))
+        .map(|entry| entry.entry_type())
+    {
+        Some(StorageEntryType::Map { key_ty, .. }) => *key_ty,
+        _ => return false,
+    };
+    metadata
+        .types()
+        .resolve(key_ty)
+        .and_then(|ty| ty.path.segments.last())
+        .map_or(false, |name| name == "AccountId20")
+}
+
 /// Convert a Composite value to a Python object.
 ///
 /// Args:
@@ -754,12 +5479,90 @@ fn decoded_value_to_py_object(py: Python, decoded_value: &Value<u32>) -> PyResul
     }
 }
 
+// Well-known development accounts exposed on the `dev` submodule
+
+/// Return the "//Alice" development keypair.
+#[pyfunction]
+#[pyo3(name = "alice")]
+fn dev_alice(py: Python) -> PyResult<Keypair> {
+    Keypair::dev(py, "alice", "sr25519")
+}
+
+/// Return the "//Bob" development keypair.
+#[pyfunction]
+#[pyo3(name = "bob")]
+fn dev_bob(py: Python) -> PyResult<Keypair> {
+    Keypair::dev(py, "bob", "sr25519")
+}
+
+/// Return the "//Charlie" development keypair.
+#[pyfunction]
+#[pyo3(name = "charlie")]
+fn dev_charlie(py: Python) -> PyResult<Keypair> {
+    Keypair::dev(py, "charlie", "sr25519")
+}
+
+/// Return the "//Dave" development keypair.
+#[pyfunction]
+#[pyo3(name = "dave")]
+fn dev_dave(py: Python) -> PyResult<Keypair> {
+    Keypair::dev(py, "dave", "sr25519")
+}
+
+/// Return the "//Eve" development keypair.
+#[pyfunction]
+#[pyo3(name = "eve")]
+fn dev_eve(py: Python) -> PyResult<Keypair> {
+    Keypair::dev(py, "eve", "sr25519")
+}
+
+/// Return the "//Ferdie" development keypair.
+#[pyfunction]
+#[pyo3(name = "ferdie")]
+fn dev_ferdie(py: Python) -> PyResult<Keypair> {
+    Keypair::dev(py, "ferdie", "sr25519")
+}
+
+/// Return the "//One" development keypair.
+#[pyfunction]
+#[pyo3(name = "one")]
+fn dev_one(py: Python) -> PyResult<Keypair> {
+    Keypair::dev(py, "one", "sr25519")
+}
+
+/// Return the "//Two" development keypair.
+#[pyfunction]
+#[pyo3(name = "two")]
+fn dev_two(py: Python) -> PyResult<Keypair> {
+    Keypair::dev(py, "two", "sr25519")
+}
+
 /// Define the Python module.
 #[pymodule]
-fn subxtpy(_py: Python, m: &PyModule) -> PyResult<()> {
+fn subxtpy(py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<SubxtClient>()?;
     m.add_class::<StorageIterator>()?;
     m.add_class::<Keypair>()?;
     m.add_class::<BlockSubscription>()?;
+    m.add_class::<TransactionProgress>()?;
+    m.add_class::<PyOfflineClient>()?;
+    m.add_class::<PyPartialExtrinsic>()?;
+    m.add_class::<Call>()?;
+    m.add("DispatchError", py.get_type::<DispatchError>())?;
+    m.add_function(wrap_pyfunction!(ss58_encode, m)?)?;
+    m.add_function(wrap_pyfunction!(py_ss58_decode, m)?)?;
+    m.add_function(wrap_pyfunction!(verify, m)?)?;
+    m.add_function(wrap_pyfunction!(multisig_account_id, m)?)?;
+
+    let dev_module = PyModule::new(py, "dev")?;
+    dev_module.add_function(wrap_pyfunction!(dev_alice, dev_module)?)?;
+    dev_module.add_function(wrap_pyfunction!(dev_bob, dev_module)?)?;
+    dev_module.add_function(wrap_pyfunction!(dev_charlie, dev_module)?)?;
+    dev_module.add_function(wrap_pyfunction!(dev_dave, dev_module)?)?;
+    dev_module.add_function(wrap_pyfunction!(dev_eve, dev_module)?)?;
+    dev_module.add_function(wrap_pyfunction!(dev_ferdie, dev_module)?)?;
+    dev_module.add_function(wrap_pyfunction!(dev_one, dev_module)?)?;
+    dev_module.add_function(wrap_pyfunction!(dev_two, dev_module)?)?;
+    m.add_submodule(dev_module)?;
     Ok(())
 }
@@ -0,0 +1,53 @@
+import pytest
+from subxtpy import SubxtClient, Keypair
+
+
+@pytest.mark.asyncio
+async def test_storage_at_block_number():
+    client = await SubxtClient.new()
+
+    assert await client.storage("System", "Number", [], at=1) == 1
+
+
+@pytest.mark.asyncio
+async def test_storage_at_block_hash():
+    client = await SubxtClient.new()
+
+    genesis = client.genesis_hash()
+    by_str = await client.storage("System", "BlockHash", [0], at=genesis)
+    by_bytes = await client.storage("System", "BlockHash", [0], at=bytes.fromhex(genesis[2:]))
+    assert by_str == by_bytes
+
+
+@pytest.mark.asyncio
+async def test_historical_nonce():
+    client = await SubxtClient.new()
+    alice = Keypair.dev("alice")
+
+    nonce = await client.runtime_api_call("AccountNonceApi", "account_nonce", [alice.public_key], at=0)
+    assert nonce == 0
+
+
+@pytest.mark.asyncio
+async def test_events_at_genesis():
+    client = await SubxtClient.new()
+
+    events = await client.events(at=0)
+    assert isinstance(events, list)
+
+
+@pytest.mark.asyncio
+async def test_unknown_block():
+    client = await SubxtClient.new()
+
+    with pytest.raises(ValueError):
+        await client.storage("System", "Number", [], at=2**32)
+
+    with pytest.raises(ValueError):
+        await client.storage("System", "Number", [], at="0x" + "00" * 32)
+
+    with pytest.raises(TypeError):
+        await client.events(at=1.5)
+
+    with pytest.raises(TypeError):
+        await client.events(at=True)
@@ -0,0 +1,80 @@
+import pytest
+from subxtpy import SubxtClient, Keypair, Call, DispatchError
+
+
+@pytest.mark.asyncio
+async def test_batch_all():
+    client = await SubxtClient.new()
+    alice = Keypair.dev("alice")
+
+    calls = [Call("System", "remark", [b"one"]), Call("System", "remark", [b"two"])]
+    result = await client.batch(alice, calls)
+
+    assert result["failed"] == []
+    assert any(e["variant"] == "BatchCompleted" for e in result["events"])
+
+
+@pytest.mark.asyncio
+async def test_batch_all_reverts():
+    client = await SubxtClient.new()
+    bob = Keypair.dev("bob")
+
+    calls = [Call("System", "remark", [b"one"]), Call("Sudo", "set_key", [bob.account_id])]
+    with pytest.raises(DispatchError):
+        await client.batch(bob, calls)
+
+
+@pytest.mark.asyncio
+async def test_batch_interrupted():
+    client = await SubxtClient.new()
+    bob = Keypair.dev("bob")
+
+    calls = [Call("System", "remark", [b"one"]), Call("Sudo", "set_key", [bob.account_id])]
+    result = await client.batch(bob, calls, mode="interrupt")
+
+    assert len(result["failed"]) == 1
+    assert result["failed"][0]["index"] == 1
+    assert result["failed"][0]["error"]["error"] == "RequireSudo"
+
+
+@pytest.mark.asyncio
+async def test_batch_nested():
+    client = await SubxtClient.new()
+    bob = Keypair.dev("bob")
+
+    nested = Call("Utility", "force_batch", [[Call("Sudo", "set_key", [bob.account_id]), Call("System", "remark", [b"two"])]])
+    calls = [nested, Call("System", "remark", [b"one"]), Call("Sudo", "set_key", [bob.account_id])]
+    result = await client.batch(bob, calls, mode="force")
+
+    # The nested batch's failed call doesn't shift the indices of the top-level calls
+    assert [failure["index"] for failure in result["failed"]] == [2]
+    assert result["failed"][0]["error"]["error"] == "RequireSudo"
+
+
+@pytest.mark.asyncio
+async def test_batch_nested_failure_after_errors():
+    client = await SubxtClient.new()
+    bob = Keypair.dev("bob")
+
+    # The first nested batch completes with errors, while the second fails and is reverted
+    with_errors = Call("Utility", "force_batch", [[Call("Sudo", "set_key", [bob.account_id])]])
+    failing = Call("Utility", "batch_all", [[Call("Sudo", "set_key", [bob.account_id])]])
+    result = await client.batch(bob, [with_errors, failing], mode="force")
+
+    assert [failure["index"] for failure in result["failed"]] == [1]
+    assert result["failed"][0]["error"]["error"] == "RequireSudo"
+
+
+@pytest.mark.asyncio
+async def test_batch_unknown_mode():
+    client = await SubxtClient.new()
+
+    with pytest.raises(ValueError):
+        await client.batch(Keypair.dev("alice"), [], mode="sometimes")
+
+
+def test_call_repr():
+    call = Call("System", "remark", [b"hello"])
+    assert call.pallet_name == "System"
+    assert call.call_name == "remark"
+    assert repr(call) == "Call(System.remark)"
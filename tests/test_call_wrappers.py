@@ -0,0 +1,61 @@
+import pytest
+from subxtpy import SubxtClient, Keypair, Call, DispatchError
+
+
+@pytest.mark.asyncio
+async def test_sudo():
+    client = await SubxtClient.new()
+    alice, bob = Keypair.dev("alice"), Keypair.dev("bob")
+
+    result = await client.sudo(alice, Call("Balances", "force_set_balance", [bob.account_id, 10**18]))
+    assert any(e["variant"] == "Sudid" for e in result["events"])
+
+
+@pytest.mark.asyncio
+async def test_sudo_inner_failure():
+    client = await SubxtClient.new()
+    alice, bob = Keypair.dev("alice"), Keypair.dev("bob")
+
+    # Sudo.sudo succeeds, but a transfer needs a signed origin rather than root
+    call = Call("Balances", "transfer_keep_alive", [bob.account_id, 1_000])
+    with pytest.raises(DispatchError):
+        await client.sudo(alice, call)
+
+
+@pytest.mark.asyncio
+async def test_sudo_nested():
+    client = await SubxtClient.new()
+    alice, bob = Keypair.dev("alice"), Keypair.dev("bob")
+
+    # The inner Sudo.sudo succeeds even though its transfer fails, and only its result is checked
+    inner = Call("Sudo", "sudo", [Call("Balances", "transfer_keep_alive", [bob.account_id, 1_000])])
+    result = await client.sudo(alice, inner)
+    assert len([e for e in result["events"] if e["variant"] == "Sudid"]) == 2
+
+
+@pytest.mark.asyncio
+async def test_sudo_unchecked_weight():
+    client = await SubxtClient.new()
+    alice = Keypair.dev("alice")
+
+    result = await client.sudo_unchecked_weight(alice, Call("System", "remark", [b"sudo"]), (1_000, 0))
+    assert any(e["variant"] == "Sudid" for e in result["events"])
+
+
+@pytest.mark.asyncio
+async def test_as_derivative():
+    client = await SubxtClient.new()
+    alice = Keypair.dev("alice")
+
+    result = await client.as_derivative(alice, 0, Call("System", "remark", [b"derivative"]))
+    assert result["extrinsic_hash"].startswith("0x")
+
+
+@pytest.mark.asyncio
+async def test_proxy_without_proxy():
+    client = await SubxtClient.new()
+    alice, bob = Keypair.dev("alice"), Keypair.dev("bob")
+
+    with pytest.raises(DispatchError) as e:
+        await client.proxy(bob, alice.account_id, Call("System", "remark", [b"proxy"]))
+    assert e.value.error == "NotProxy"
@@ -0,0 +1,22 @@
+import pytest
+from subxtpy import SubxtClient, Keypair, dev
+
+
+def test_dev_keypairs():
+    for name in ["alice", "bob", "charlie", "dave", "eve", "ferdie", "one", "two"]:
+        assert isinstance(Keypair.dev(name), Keypair)
+        assert isinstance(getattr(dev, name)(), Keypair)
+
+
+def test_unknown_dev_keypair():
+    with pytest.raises(ValueError):
+        Keypair.dev("mallory")
+
+
+@pytest.mark.asyncio
+async def test_transfer_from_dev_account():
+    client = await SubxtClient.new()
+
+    transfer_payload = ["8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48", 1_000]
+    tx_hash = await client.sign_and_submit(dev.alice(), "Balances", "transfer_allow_death", transfer_payload)
+    assert tx_hash.startswith("0x")
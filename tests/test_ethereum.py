@@ -0,0 +1,31 @@
+import pytest
+from subxtpy import Keypair, verify
+
+def test_ethereum_dev_keypair():
+    keypair = Keypair.dev("alith", scheme="ethereum")
+    assert keypair.scheme == "ethereum"
+
+    keypair = Keypair.from_secret_key("5fb92d6e98884f76de468fa3f6278f8807c48bebc13595d45af5bdc4da702133",
+                                      scheme="ethereum")
+    assert keypair.scheme == "ethereum"
+
+
+def test_ethereum_keypair_rejects_uri():
+    with pytest.raises(ValueError):
+        Keypair.from_uri("//Alice", scheme="ethereum")
+
+
+
+# Alith, the first Moonbeam dev account
+ALITH_SECRET_KEY = "5fb92d6e98884f76de468fa3f6278f8807c48bebc13595d45af5bdc4da702133"
+ALITH_ADDRESS = "0xf24FF3a9CF04c71Dbc94D0b566f7A27B94566cac"
+
+
+def test_ethereum_account_id20_round_trip():
+    alith = Keypair.from_secret_key(ALITH_SECRET_KEY, scheme="ethereum")
+    assert "0x" + alith.account_id == ALITH_ADDRESS.lower()
+
+    signature = alith.sign(b"Hello")
+    address = bytes.fromhex(ALITH_ADDRESS[2:])
+    assert verify(signature, b"Hello", address, scheme="ethereum")
+    assert not verify(signature, b"Goodbye", address, scheme="ethereum")
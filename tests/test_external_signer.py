@@ -0,0 +1,41 @@
+import pytest
+from subxtpy import SubxtClient, Keypair
+
+
+class ExternalSigner:
+    """Stands in for a KMS/HSM: only exposes the account id and a signing function."""
+
+    def __init__(self, keypair):
+        self._keypair = keypair
+        self.scheme = keypair.scheme
+
+    def account_id(self):
+        return self._keypair.account_id
+
+    def sign(self, payload):
+        return self._keypair.sign(payload)
+
+
+class BrokenSigner:
+    def account_id(self):
+        return Keypair.dev("alice").account_id
+
+    def sign(self, payload):
+        return b"not a signature"
+
+
+@pytest.mark.asyncio
+async def test_external_signer_submit():
+    client = await SubxtClient.new()
+    signer = ExternalSigner(Keypair.dev("bob"))
+
+    tx_hash = await client.sign_and_submit(signer, "System", "remark", [b"external signer"])
+    assert tx_hash.startswith("0x")
+
+
+@pytest.mark.asyncio
+async def test_external_signer_invalid_signature():
+    client = await SubxtClient.new()
+
+    with pytest.raises(ValueError):
+        await client.sign_and_submit(BrokenSigner(), "System", "remark", [b"external signer"])
@@ -0,0 +1,88 @@
+import pytest
+from subxtpy import SubxtClient, Keypair
+
+ALICE = Keypair.dev("alice")
+BOB = Keypair.dev("bob")
+
+
+@pytest.mark.asyncio
+async def test_estimate_fee():
+    client = await SubxtClient.new()
+
+    fee = await client.estimate_fee(ALICE, "Balances", "transfer_keep_alive", [BOB.account_id, 1_000])
+    assert fee["partial_fee"] > 0
+    assert "fee_details" in fee
+
+    tipped = await client.estimate_fee(ALICE, "Balances", "transfer_keep_alive", [BOB.account_id, 1_000], tip=500)
+    assert tipped["fee_details"]["tip"] == 500
+
+
+@pytest.mark.asyncio
+async def test_dry_run_success():
+    client = await SubxtClient.new()
+
+    result = await client.dry_run(ALICE, "System", "remark", [b"dry run"])
+    assert result["success"]
+
+
+@pytest.mark.asyncio
+async def test_dry_run_mortal():
+    client = await SubxtClient.new()
+
+    result = await client.dry_run(ALICE, "System", "remark", [b"dry run"], mortality_blocks=64)
+    assert result["success"]
+
+
+@pytest.mark.asyncio
+async def test_dry_run_dispatch_error():
+    client = await SubxtClient.new()
+
+    result = await client.dry_run(BOB, "Sudo", "set_key", [ALICE.account_id])
+    assert not result["success"]
+    assert result["dispatch_error"]["pallet"] == "Sudo"
+    assert result["dispatch_error"]["error"] == "RequireSudo"
+
+
+@pytest.mark.asyncio
+async def test_dry_run_validity_error():
+    client = await SubxtClient.new()
+
+    result = await client.dry_run(ALICE, "System", "remark", [b"dry run"], nonce=0)
+    assert not result["success"]
+    assert "validity_error" in result
+
+
+async def has_dry_run_api(client):
+    try:
+        await client.dry_run(ALICE, "System", "remark", [b"dry run"], method="dry_run_api")
+    except RuntimeError as e:
+        if "no DryRunApi" in str(e):
+            return False
+        raise
+    return True
+
+
+@pytest.mark.asyncio
+@pytest.mark.parametrize("method", ["dry_run_api", "system_dry_run"])
+async def test_dry_run_methods(method):
+    client = await SubxtClient.new()
+    if method == "dry_run_api" and not await has_dry_run_api(client):
+        pytest.skip("The runtime has no DryRunApi")
+
+    result = await client.dry_run(ALICE, "System", "remark", [b"dry run"], method=method, mortality_blocks=64)
+    assert result["success"]
+
+    result = await client.dry_run(BOB, "Sudo", "set_key", [ALICE.account_id], method=method)
+    assert result["dispatch_error"]["error"] == "RequireSudo"
+
+    result = await client.dry_run(ALICE, "System", "remark", [b"dry run"], method=method, nonce=0)
+    assert not result["success"]
+    assert "validity_error" in result
+
+
+@pytest.mark.asyncio
+async def test_dry_run_unknown_method():
+    client = await SubxtClient.new()
+
+    with pytest.raises(ValueError):
+        await client.dry_run(ALICE, "System", "remark", [b"dry run"], method="guess")
@@ -0,0 +1,37 @@
+import pytest
+from subxtpy import SubxtClient, Keypair
+
+ALICE_ACCOUNT_ID = "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"
+ALICE_SS58_ADDRESS = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+
+
+def test_keypair_account_id():
+    alice = Keypair.dev("alice")
+    assert alice.public_key == bytes.fromhex(ALICE_ACCOUNT_ID)
+    assert alice.account_id == ALICE_ACCOUNT_ID
+    assert alice.ss58_address() == ALICE_SS58_ADDRESS
+    assert alice.ss58_address(0) != ALICE_SS58_ADDRESS
+
+
+def test_ecdsa_keypair_account_id():
+    keypair = Keypair.dev("alice", scheme="ecdsa")
+    assert len(keypair.public_key) == 33
+    assert len(keypair.account_id) == 64
+
+
+def test_ethereum_keypair_account_id():
+    alith = Keypair.dev("alith", scheme="ethereum")
+    assert alith.account_id == "f24ff3a9cf04c71dbc94d0b566f7a27b94566cac"
+    with pytest.raises(ValueError):
+        alith.ss58_address()
+
+
+@pytest.mark.asyncio
+async def test_ss58_address_with_chain_prefix():
+    client = await SubxtClient.new()
+    prefix = client.ss58_prefix()
+    assert isinstance(prefix, int)
+
+    account_info = await client.storage("System", "Account", [Keypair.dev("alice").account_id])
+    assert 'data' in account_info
+    assert isinstance(Keypair.dev("alice").ss58_address(prefix), str)
@@ -0,0 +1,41 @@
+import pytest
+from subxtpy import Keypair
+
+DEV_PHRASE = "bottom drive obey lake curtain smoke basket hold race lonely fit walk"
+
+# Public keys as printed by `subkey inspect` for the dev phrase and its derived accounts
+DEV_PHRASE_PUBLIC_KEY = "46ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a"
+ALICE_PUBLIC_KEY = "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"
+ALICE_STASH_PUBLIC_KEY = "be5ddb1579b72e84524fc29e78609e3caf42e85aa118ebfe0b0ad404b5bdd25f"
+
+
+def test_keypair_from_phrase():
+    keypair = Keypair.from_phrase(DEV_PHRASE)
+    assert isinstance(keypair, Keypair)
+
+    keypair_with_password = Keypair.from_phrase(DEV_PHRASE, password="secret")
+    assert isinstance(keypair_with_password, Keypair)
+
+
+def test_keypair_from_uri():
+    keypair = Keypair.from_uri("//Alice")
+    assert isinstance(keypair, Keypair)
+
+    keypair = Keypair.from_uri(DEV_PHRASE + "//Alice//stash/0///pw")
+    assert isinstance(keypair, Keypair)
+
+
+def test_keypair_invalid_phrase():
+    with pytest.raises(ValueError):
+        Keypair.from_phrase("not a valid mnemonic")
+
+
+def test_keypair_from_phrase_known_answer():
+    keypair = Keypair.from_phrase(DEV_PHRASE)
+    assert keypair.public_key.hex() == DEV_PHRASE_PUBLIC_KEY
+
+
+def test_keypair_from_uri_known_answer():
+    assert Keypair.from_uri("//Alice").public_key.hex() == ALICE_PUBLIC_KEY
+    assert Keypair.from_uri(DEV_PHRASE + "//Alice").public_key.hex() == ALICE_PUBLIC_KEY
+    assert Keypair.from_uri("//Alice//stash").public_key.hex() == ALICE_STASH_PUBLIC_KEY
@@ -0,0 +1,86 @@
+import base64
+import json
+import struct
+from pathlib import Path
+
+import pytest
+from subxtpy import Keypair
+
+DEV_PHRASE = "bottom drive obey lake curtain smoke basket hold race lonely fit walk"
+
+# An ed25519 keystore in the polkadot-js v3 format, holding the RFC 8032 test 1 key. It was
+# encrypted independently of subxtpy, so it checks the scrypt, secretbox and PKCS8 decoding.
+KEYSTORE_FIXTURE = Path(__file__).parent / "fixtures" / "ed25519_keystore.json"
+KEYSTORE_PASSWORD = "correct horse battery staple"
+KEYSTORE_ADDRESS = "5Gw54ghuAHodDGAS91DUxqvKa6PeT9bhDdns3ztBupY8pSyn"
+KEYSTORE_PUBLIC_KEY = "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+
+
+def test_json_fixture():
+    keypair = Keypair.from_json(KEYSTORE_FIXTURE.read_text(), KEYSTORE_PASSWORD)
+
+    assert keypair.scheme == "ed25519"
+    assert keypair.public_key.hex() == KEYSTORE_PUBLIC_KEY
+    assert keypair.ss58_address() == KEYSTORE_ADDRESS
+
+
+@pytest.mark.parametrize("scheme", ["sr25519", "ed25519", "ecdsa"])
+def test_json_round_trip(scheme):
+    keypair = Keypair.from_phrase(DEV_PHRASE, scheme=scheme)
+    keystore = keypair.to_json("password", name="Dev")
+
+    data = json.loads(keystore)
+    assert data["encoding"]["content"] == ["pkcs8", scheme]
+    assert data["encoding"]["version"] == "3"
+    assert data["address"] == keypair.ss58_address()
+    assert data["meta"]["name"] == "Dev"
+
+    restored = Keypair.from_json(keystore, "password")
+    assert restored.scheme == scheme
+    assert restored.public_key == keypair.public_key
+    assert restored.to_json("password") is not None
+
+
+def test_json_round_trip_ethereum():
+    alith = Keypair.dev("alith", scheme="ethereum")
+    keystore = alith.to_json("password")
+
+    assert json.loads(keystore)["address"] == "0x" + alith.account_id
+    assert Keypair.from_json(keystore, "password").account_id == alith.account_id
+
+
+@pytest.mark.parametrize(
+    "scheme, uri",
+    [
+        ("sr25519", "//Alice"),
+        ("sr25519", DEV_PHRASE + "//hard/soft"),
+        ("ecdsa", "//Alice"),
+        ("ecdsa", DEV_PHRASE + "//hard//1"),
+    ],
+)
+def test_json_round_trip_derived(scheme, uri):
+    keypair = Keypair.from_uri(uri, scheme=scheme)
+    keystore = keypair.to_json("password")
+
+    # The exported secret must be the derived one, not the root seed
+    restored = Keypair.from_json(keystore, "password")
+    assert restored.public_key == keypair.public_key
+    assert restored.account_id == keypair.account_id
+
+
+def test_json_wrong_password():
+    keystore = Keypair.from_phrase(DEV_PHRASE).to_json("password")
+
+    with pytest.raises(ValueError):
+        Keypair.from_json(keystore, "wrong password")
+
+
+def test_json_rejects_unsupported_scrypt_params():
+    data = json.loads(Keypair.from_phrase(DEV_PHRASE).to_json("password"))
+    encoded = bytearray(base64.b64decode(data["encoded"]))
+    # N is stored as a little-endian u32 right after the 32-byte salt
+    encoded[32:36] = struct.pack("<I", 1 << 30)
+    data["encoded"] = base64.b64encode(bytes(encoded)).decode()
+
+    with pytest.raises(ValueError, match="scrypt"):
+        Keypair.from_json(json.dumps(data), "password")
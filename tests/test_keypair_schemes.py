@@ -0,0 +1,47 @@
+import pytest
+from subxtpy import Keypair, verify
+
+# The //Alice session keys of the Substrate dev chains (GRANDPA and BEEFY)
+ALICE_ED25519_PUBLIC_KEY = "88dc3417d5058ec4b4503e0c12ea1a0a89be200fe98922423d4334014fa6b0ee"
+ALICE_ECDSA_PUBLIC_KEY = "020a1091341fe5664bfa1782d5e04779689068c916b04cb365ec3153755684d9a1"
+
+# Test 1 from RFC 8032, section 7.1
+RFC8032_SECRET_KEY = "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60"
+RFC8032_PUBLIC_KEY = "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+RFC8032_SIGNATURE = (
+    "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555"
+    "fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b"
+)
+
+
+@pytest.mark.parametrize("scheme", ["sr25519", "ed25519", "ecdsa"])
+def test_keypair_schemes(scheme):
+    keypair = Keypair.from_uri("//Alice", scheme=scheme)
+    assert keypair.scheme == scheme
+
+    keypair = Keypair.from_secret_key("e5be9a5092b81bca64be81d212e7f2f9eba183bb7a90954f7b76361f6edb5c0a", scheme=scheme)
+    assert keypair.scheme == scheme
+
+
+def test_ed25519_rejects_soft_junctions():
+    with pytest.raises(ValueError):
+        Keypair.from_uri("//Alice/soft", scheme="ed25519")
+
+
+
+@pytest.mark.parametrize("scheme,public_key", [
+    ("ed25519", ALICE_ED25519_PUBLIC_KEY),
+    ("ecdsa", ALICE_ECDSA_PUBLIC_KEY),
+])
+def test_keypair_schemes_known_answer(scheme, public_key):
+    keypair = Keypair.from_uri("//Alice", scheme=scheme)
+    assert keypair.public_key.hex() == public_key
+
+    signature = keypair.sign(b"Hello")
+    assert verify(signature, b"Hello", bytes.fromhex(public_key), scheme=scheme)
+
+
+def test_ed25519_rfc8032_vector():
+    keypair = Keypair.from_secret_key(RFC8032_SECRET_KEY, scheme="ed25519")
+    assert keypair.public_key.hex() == RFC8032_PUBLIC_KEY
+    assert keypair.sign(b"").hex() == RFC8032_SIGNATURE
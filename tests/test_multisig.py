@@ -0,0 +1,45 @@
+import hashlib
+
+import pytest
+from subxtpy import SubxtClient, Keypair, multisig_account_id
+
+ALICE = Keypair.dev("alice")
+BOB = Keypair.dev("bob")
+CHARLIE = Keypair.dev("charlie")
+SIGNATORIES = [ALICE.account_id, BOB.account_id, CHARLIE.account_id]
+
+
+def test_multisig_account_id():
+    who = b"".join(sorted(bytes.fromhex(a) for a in SIGNATORIES))
+    # 3 signatories encode as compact(3) == 0x0c, threshold as a little-endian u16
+    data = b"modlpy/utilisuba" + b"\x0c" + who + (2).to_bytes(2, "little")
+    expected = hashlib.blake2b(data, digest_size=32).hexdigest()
+
+    assert multisig_account_id(SIGNATORIES, 2) == expected
+    assert multisig_account_id(list(reversed(SIGNATORIES)), 2) == expected
+    assert multisig_account_id([ALICE.ss58_address(), BOB.account_id, CHARLIE.account_id], 2) == expected
+
+
+@pytest.mark.asyncio
+async def test_multisig_approve_and_cancel():
+    client = await SubxtClient.new()
+    call = ("System", "remark", [b"multisig"])
+    call_hash = client.call_hash(*call)
+    multisig = multisig_account_id(SIGNATORIES, 2)
+
+    progress = await client.multisig_approve(ALICE, SIGNATORIES, 2, *call)
+    await progress.wait_for_finalized_success()
+    timepoint = await client.multisig_timepoint(multisig, call_hash)
+    assert timepoint is not None
+
+    progress = await client.multisig_cancel(ALICE, SIGNATORIES, 2, call_hash)
+    await progress.wait_for_finalized_success()
+    assert await client.multisig_timepoint(multisig, call_hash) is None
+
+
+@pytest.mark.asyncio
+async def test_multisig_signer_not_a_signatory():
+    client = await SubxtClient.new()
+
+    with pytest.raises(ValueError):
+        await client.multisig_approve(Keypair.dev("dave"), SIGNATORIES, 2, "System", "remark", [b"multisig"])
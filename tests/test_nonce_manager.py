@@ -0,0 +1,42 @@
+import asyncio
+
+import pytest
+from subxtpy import SubxtClient, Keypair
+
+
+@pytest.mark.asyncio
+async def test_concurrent_submissions():
+    client = await SubxtClient.new()
+    charlie = Keypair.dev("charlie")
+
+    tx_hashes = await asyncio.gather(
+        *(client.sign_and_submit(charlie, "System", "remark", [f"concurrent {i}".encode()]) for i in range(10))
+    )
+    assert len(set(tx_hashes)) == 10
+
+
+@pytest.mark.asyncio
+async def test_resync_after_failed_submission():
+    client = await SubxtClient.new()
+    dave = Keypair.dev("dave")
+
+    await client.sign_and_submit(dave, "System", "remark", [b"before"])
+    with pytest.raises(RuntimeError):
+        # Stale nonce: rejected by the pool
+        await client.sign_and_submit(dave, "System", "remark", [b"stale"], nonce=0)
+
+    progress = await client.sign_and_submit_and_watch(dave, "System", "remark", [b"after"])
+    await progress.wait_for_finalized_success()
+
+
+@pytest.mark.asyncio
+async def test_explicit_nonce_is_tracked():
+    client = await SubxtClient.new()
+    eve = Keypair.dev("eve")
+    account_info = await client.storage("System", "Account", [eve.account_id])
+
+    explicit = await client.create_signed(eve, "System", "remark", [b"explicit"], nonce=account_info["nonce"])
+    managed = await client.create_signed(eve, "System", "remark", [b"managed"])
+    await client.submit_raw(explicit)
+    # Rejected by the pool if the managed nonce didn't follow the explicit one
+    await client.submit_raw(managed)
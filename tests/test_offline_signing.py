@@ -0,0 +1,54 @@
+import pytest
+from subxtpy import SubxtClient, OfflineClient, Keypair
+
+
+async def offline_client(client, tmp_path):
+    path = tmp_path / "metadata.scale"
+    path.write_bytes(await client.metadata())
+    version = client.runtime_version()
+    return OfflineClient.from_metadata_file(
+        str(path), client.genesis_hash(), version["spec_version"], version["transaction_version"]
+    )
+
+
+@pytest.mark.asyncio
+async def test_sign_offline_and_submit(tmp_path):
+    client = await SubxtClient.new()
+    alice = Keypair.dev("alice")
+    nonce = await client.runtime_api_call("AccountNonceApi", "account_nonce", [alice.public_key])
+
+    offline = await offline_client(client, tmp_path)
+    extrinsic = offline.create_signed(alice, "System", "remark", [b"offline"], nonce=nonce)
+
+    assert isinstance(extrinsic, bytes)
+    tx_hash = await client.submit_raw(extrinsic)
+    assert tx_hash.startswith("0x")
+
+
+@pytest.mark.asyncio
+async def test_create_signed_then_submit_raw():
+    client = await SubxtClient.new()
+    alice = Keypair.dev("alice")
+
+    extrinsic = await client.create_signed(alice, "System", "remark", [b"signed"])
+    tx_hash = await client.submit_raw(extrinsic)
+    assert tx_hash.startswith("0x")
+
+
+@pytest.mark.asyncio
+async def test_offline_requires_nonce(tmp_path):
+    client = await SubxtClient.new()
+    offline = await offline_client(client, tmp_path)
+
+    with pytest.raises(ValueError):
+        offline.create_signed(Keypair.dev("alice"), "System", "remark", [b"offline"])
+
+    with pytest.raises(ValueError):
+        offline.create_signed(
+            Keypair.dev("alice"), "System", "remark", [b"offline"], nonce=0, mortality_blocks=64
+        )
+
+
+def test_invalid_metadata():
+    with pytest.raises(ValueError):
+        OfflineClient(b"not metadata", "0x" + "00" * 32, 1, 1)
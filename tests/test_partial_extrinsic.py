@@ -0,0 +1,45 @@
+import json
+
+import pytest
+from subxtpy import SubxtClient, Keypair
+
+
+@pytest.mark.asyncio
+async def test_create_partial_and_attach_signature():
+    client = await SubxtClient.new()
+    alice = Keypair.dev("alice")
+
+    partial = await client.create_partial("System", "remark", [b"cold wallet"], alice.account_id)
+    breakdown = json.loads(partial.to_json())
+    assert breakdown["pallet"] == "System"
+    assert breakdown["call"] == "remark"
+    assert breakdown["signer_payload"] == "0x" + partial.signer_payload.hex()
+
+    extrinsic = client.attach_signature(partial, alice.sign(partial.signer_payload))
+    tx_hash = await client.submit_raw(extrinsic)
+    assert tx_hash.startswith("0x")
+
+
+@pytest.mark.asyncio
+async def test_create_partial_uses_chain_nonce():
+    client = await SubxtClient.new()
+    ferdie = Keypair.dev("ferdie")
+
+    first = await client.create_partial("System", "remark", [b"first"], ferdie.account_id)
+    second = await client.create_partial("System", "remark", [b"second"], ferdie.account_id)
+    account_info = await client.storage("System", "Account", [ferdie.account_id])
+
+    assert json.loads(first.to_json())["nonce"] == account_info["nonce"]
+    assert json.loads(second.to_json())["nonce"] == account_info["nonce"]
+
+
+@pytest.mark.asyncio
+async def test_attach_signature_wrong_length():
+    client = await SubxtClient.new()
+    alice = Keypair.dev("alice")
+
+    partial = await client.create_partial(
+        "System", "remark", [b"cold wallet"], alice.account_id, nonce=0
+    )
+    with pytest.raises(ValueError):
+        client.attach_signature(partial, b"\x00" * 65)
@@ -0,0 +1,34 @@
+import pytest
+from subxtpy import Keypair, verify
+
+
+@pytest.mark.parametrize("scheme", ["sr25519", "ed25519", "ecdsa"])
+def test_sign_and_verify(scheme):
+    keypair = Keypair.dev("alice", scheme=scheme)
+    signature = keypair.sign(b"Hello")
+
+    assert verify(signature, b"Hello", keypair.public_key, scheme=scheme)
+    assert not verify(signature, b"Goodbye", keypair.public_key, scheme=scheme)
+
+
+def test_sign_and_verify_wrapped():
+    alice = Keypair.dev("alice")
+    signature = alice.sign(b"Hello", wrap=True)
+
+    assert verify(signature, b"<Bytes>Hello</Bytes>", alice.public_key)
+    assert verify(signature, b"Hello", alice.public_key, wrap=True)
+    assert not verify(signature, b"Hello", alice.public_key)
+
+
+def test_ethereum_sign_and_verify():
+    alith = Keypair.dev("alith", scheme="ethereum")
+    signature = alith.sign(b"Hello")
+
+    assert len(signature) == 65
+    assert verify(signature, b"Hello", alith.public_key, scheme="ethereum")
+    assert verify(signature, b"Hello", bytes.fromhex(alith.account_id), scheme="ethereum")
+
+
+def test_verify_invalid_length():
+    with pytest.raises(ValueError):
+        verify(b"short", b"Hello", Keypair.dev("alice").public_key)
@@ -0,0 +1,42 @@
+import pytest
+from subxtpy import SubxtClient, ss58_encode, ss58_decode
+
+ALICE_ACCOUNT_ID = "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"
+ALICE_SS58_ADDRESS = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+
+
+def test_ss58_round_trip():
+    assert ss58_encode(bytes.fromhex(ALICE_ACCOUNT_ID)) == ALICE_SS58_ADDRESS
+    assert ss58_decode(ALICE_SS58_ADDRESS) == (bytes.fromhex(ALICE_ACCOUNT_ID), 42)
+
+    for prefix in [0, 2, 63, 64, 1284, 16383]:
+        address = ss58_encode(bytes.fromhex(ALICE_ACCOUNT_ID), prefix)
+        assert ss58_decode(address) == (bytes.fromhex(ALICE_ACCOUNT_ID), prefix)
+
+
+def test_ss58_short_payload():
+    # 1-byte payloads use a 1-byte checksum, matching polkadot-js `encodeAddress`
+    assert ss58_encode(bytes([1])) == "F7NZ"
+    assert ss58_decode("F7NZ") == (bytes([1]), 42)
+
+    payload = bytes(range(1, 9))
+    assert ss58_encode(payload) == "3MsZWNhRvzMGK9"
+    assert ss58_decode("3MsZWNhRvzMGK9") == (payload, 42)
+
+
+def test_ss58_invalid_length():
+    with pytest.raises(ValueError):
+        ss58_encode(bytes(20))
+
+
+def test_ss58_invalid_checksum():
+    with pytest.raises(ValueError):
+        ss58_decode(ALICE_SS58_ADDRESS[:-1] + "Z")
+
+
+@pytest.mark.asyncio
+async def test_storage_with_ss58_key():
+    client = await SubxtClient.new()
+
+    account_info = await client.storage("System", "Account", [ALICE_SS58_ADDRESS])
+    assert 'data' in account_info
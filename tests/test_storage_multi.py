@@ -0,0 +1,44 @@
+import pytest
+from subxtpy import SubxtClient, Keypair
+
+
+@pytest.mark.asyncio
+async def test_storage_multi_in_order():
+    client = await SubxtClient.new()
+    names = ["alice", "bob", "charlie"]
+    keys = [[Keypair.dev(name).account_id] for name in names]
+    keys.insert(1, [Keypair.from_uri("//NeverFunded").account_id])
+
+    block = await client.storage("System", "Number", [])
+    accounts = await client.storage_multi("System", "Account", keys, at=block)
+
+    assert len(accounts) == 4
+    assert accounts[1]["data"]["free"] == 0
+    for key, account in zip(keys, accounts):
+        assert account == await client.storage_or_default("System", "Account", key, at=block)
+
+
+@pytest.mark.asyncio
+async def test_storage_multi_option_query():
+    client = await SubxtClient.new()
+    keys = [[Keypair.dev(name).account_id, bytes(32)] for name in ["alice", "bob"]]
+
+    # Multisig.Multisigs is declared with OptionQuery, so absent entries have no default
+    assert await client.storage_multi("Multisig", "Multisigs", keys) == [None, None]
+
+
+@pytest.mark.asyncio
+async def test_storage_multi_many_keys():
+    client = await SubxtClient.new()
+    keys = [[Keypair.from_uri(f"//Account{i}").account_id] for i in range(600)]
+
+    accounts = await client.storage_multi("System", "Account", keys)
+    assert len(accounts) == 600
+
+
+@pytest.mark.asyncio
+async def test_storage_multi_unknown_entry():
+    client = await SubxtClient.new()
+
+    with pytest.raises(ValueError):
+        await client.storage_multi("System", "NoSuchEntry", [[]])
@@ -0,0 +1,41 @@
+import pytest
+from subxtpy import SubxtClient, Keypair
+
+
+@pytest.mark.asyncio
+async def test_value_query_default():
+    client = await SubxtClient.new()
+    fresh = Keypair.from_uri("//NeverFunded")
+
+    with pytest.raises(ValueError):
+        await client.storage("System", "Account", [fresh.account_id])
+
+    account = await client.storage_or_default("System", "Account", [fresh.account_id])
+    assert account["nonce"] == 0
+    assert account["data"]["free"] == 0
+
+
+@pytest.mark.asyncio
+async def test_option_query_none():
+    client = await SubxtClient.new()
+    fresh = Keypair.from_uri("//NeverFunded")
+
+    assert await client.storage_or_default("Proxy", "Announcements", [fresh.account_id]) is not None
+    assert await client.storage_or_default("Multisig", "Multisigs", [fresh.account_id, b"\x00" * 32]) is None
+
+
+@pytest.mark.asyncio
+async def test_present_entry():
+    client = await SubxtClient.new()
+    alice = Keypair.dev("alice")
+
+    account = await client.storage_or_default("System", "Account", [alice.account_id])
+    assert account["data"]["free"] > 0
+
+
+@pytest.mark.asyncio
+async def test_unknown_entry():
+    client = await SubxtClient.new()
+
+    with pytest.raises(ValueError):
+        await client.storage_or_default("System", "NoSuchEntry", [])
@@ -0,0 +1,58 @@
+import pytest
+from subxtpy import SubxtClient, Keypair
+
+
+@pytest.mark.asyncio
+async def test_submit_with_tip_and_mortality():
+    client = await SubxtClient.new()
+    alice = Keypair.dev("alice")
+
+    progress = await client.sign_and_submit_and_watch(
+        alice, "System", "remark", [b"tx params"], tip=1_000, mortality_blocks=64
+    )
+    result = await progress.wait_for_finalized_success()
+
+    fee_paid = [e for e in result["events"] if e["variant"] == "TransactionFeePaid"]
+    assert fee_paid and fee_paid[0]["fields"]["tip"] == 1_000
+
+
+@pytest.mark.asyncio
+async def test_submit_with_stale_nonce():
+    client = await SubxtClient.new()
+    alice = Keypair.dev("alice")
+
+    with pytest.raises(RuntimeError):
+        await client.sign_and_submit(alice, "System", "remark", [b"tx params"], nonce=0)
+
+
+@pytest.mark.asyncio
+async def test_submit_with_extras():
+    client = await SubxtClient.new()
+    alice = Keypair.dev("alice")
+
+    # Extensions are encoded against the metadata by name, so this sets the tip
+    progress = await client.sign_and_submit_and_watch(
+        alice, "System", "remark", [b"tx params"], extras={"ChargeTransactionPayment": 1_000}
+    )
+    result = await progress.wait_for_finalized_success()
+
+    fee_paid = [e for e in result["events"] if e["variant"] == "TransactionFeePaid"]
+    assert fee_paid and fee_paid[0]["fields"]["tip"] == 1_000
+
+
+@pytest.mark.asyncio
+async def test_submit_with_unknown_extra():
+    client = await SubxtClient.new()
+
+    with pytest.raises(RuntimeError, match="CheckAppId"):
+        await client.sign_and_submit(
+            Keypair.dev("alice"), "System", "remark", [b"tx params"], extras={"CheckAppId": 1}
+        )
+
+
+@pytest.mark.asyncio
+async def test_unknown_param():
+    client = await SubxtClient.new()
+
+    with pytest.raises(TypeError):
+        await client.sign_and_submit(Keypair.dev("alice"), "System", "remark", [b"tx params"], priority=1)
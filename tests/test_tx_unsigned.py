@@ -0,0 +1,51 @@
+import hashlib
+import json
+import urllib.request
+
+import pytest
+from subxtpy import SubxtClient, Keypair, Call
+
+NODE_HTTP_URL = "http://127.0.0.1:9944"
+
+
+def runtime_code():
+    # The node serves JSON-RPC over HTTP on the same port as the websocket
+    request = urllib.request.Request(
+        NODE_HTTP_URL,
+        data=json.dumps({"jsonrpc": "2.0", "id": 1, "method": "state_getStorage", "params": ["0x3a636f6465"]}).encode(),
+        headers={"Content-Type": "application/json"},
+    )
+    with urllib.request.urlopen(request) as response:
+        return bytes.fromhex(json.load(response)["result"][2:])
+
+
+@pytest.mark.asyncio
+async def test_unsigned_included():
+    client = await SubxtClient.new()
+    code = runtime_code()
+
+    # Once an upgrade is authorized, anyone can apply it with an unsigned transaction. Reapplying
+    # the current runtime leaves the chain as it is.
+    code_hash = hashlib.blake2b(code, digest_size=32).hexdigest()
+    await client.sudo(Keypair.dev("alice"), Call("System", "authorize_upgrade_without_checks", [code_hash]))
+
+    progress = await client.submit_unsigned_and_watch("System", "apply_authorized_upgrade", [code])
+    result = await progress.wait_for_finalized_success()
+    assert any(e["pallet"] == "System" and e["variant"] == "CodeUpdated" for e in result["events"])
+
+
+@pytest.mark.asyncio
+async def test_unsigned_rejected():
+    client = await SubxtClient.new()
+
+    # System.remark requires a signed origin, so the pool rejects it as unsigned
+    with pytest.raises(RuntimeError):
+        await client.submit_unsigned("System", "remark", [b"unsigned"])
+
+
+@pytest.mark.asyncio
+async def test_unsigned_unknown_call():
+    client = await SubxtClient.new()
+
+    with pytest.raises(RuntimeError):
+        await client.submit_unsigned("System", "no_such_call", [])
@@ -0,0 +1,66 @@
+import pytest
+from subxtpy import SubxtClient, Keypair, DispatchError
+
+
+@pytest.mark.asyncio
+async def test_watch_until_finalized():
+    client = await SubxtClient.new()
+    alice = Keypair.dev("alice")
+
+    progress = await client.sign_and_submit_and_watch(alice, "System", "remark", [b"watch"])
+    assert progress.tx_hash.startswith("0x")
+
+    statuses = [status async for status in progress]
+    assert statuses[-1]["status"] == "InFinalizedBlock"
+    assert statuses[-1]["extrinsic_hash"] == progress.tx_hash
+
+
+@pytest.mark.asyncio
+async def test_wait_for_finalized_success():
+    client = await SubxtClient.new()
+    bob = Keypair.dev("bob")
+
+    progress = await client.sign_and_submit_and_watch(
+        Keypair.dev("alice"), "Balances", "transfer_keep_alive", [bob.account_id, 1_000]
+    )
+    result = await progress.wait_for_finalized_success()
+
+    assert result["block_hash"].startswith("0x")
+    assert result["extrinsic_hash"] == progress.tx_hash
+    events = [(event["pallet"], event["variant"]) for event in result["events"]]
+    assert ("Balances", "Transfer") in events
+    assert ("System", "ExtrinsicSuccess") in events
+
+    with pytest.raises(RuntimeError):
+        await progress.wait_for_finalized_success()
+
+
+@pytest.mark.asyncio
+async def test_wait_for_finalized_dispatch_error():
+    client = await SubxtClient.new()
+    alice = Keypair.dev("alice")
+
+    # Only the sudo key (Alice on dev chains) may call Sudo
+    progress = await client.sign_and_submit_and_watch(Keypair.dev("bob"), "Sudo", "set_key", [alice.account_id])
+    with pytest.raises(DispatchError) as exc_info:
+        await progress.wait_for_finalized_success()
+
+    error = exc_info.value
+    assert isinstance(error, RuntimeError)
+    assert error.pallet == "Sudo"
+    assert error.error == "RequireSudo"
+    assert error.docs
+    assert isinstance(error.pallet_index, int)
+    assert error.error_bytes == bytes([0, 0, 0, 0])
+
+
+@pytest.mark.asyncio
+async def test_wait_for_finalized_bad_origin():
+    client = await SubxtClient.new()
+
+    progress = await client.sign_and_submit_and_watch(Keypair.dev("bob"), "System", "set_heap_pages", [1])
+    with pytest.raises(DispatchError) as exc_info:
+        await progress.wait_for_finalized_success()
+
+    assert exc_info.value.pallet is None
+    assert exc_info.value.error == "BadOrigin"